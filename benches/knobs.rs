@@ -0,0 +1,62 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use egui_knob::egui::{self, Context, RawInput, Rect, pos2, vec2};
+use egui_knob::{Knob, KnobConfig, KnobPainter, KnobStyle, LabelPosition};
+
+/// Number of knobs in a large mixer view
+const KNOBS: usize = 256;
+
+/// A frame of a mixer with `KNOBS` labeled knobs in a grid
+fn mixer_frame(ctx: &Context, values: &mut [f32]) {
+    let input = RawInput {
+        screen_rect: Some(Rect::from_min_size(pos2(0.0, 0.0), vec2(1920.0, 1080.0))),
+        ..Default::default()
+    };
+    let _ = ctx.run_ui(input, |ui| {
+        egui::Grid::new("mixer").show(ui, |ui| {
+            for (i, value) in values.iter_mut().enumerate() {
+                ui.add(
+                    Knob::new(value, 0.0, 1.0, KnobStyle::Wiper)
+                        .with_label(format!("Ch {i}"), LabelPosition::Bottom)
+                        .with_unit("dB")
+                        .with_size(32.0),
+                );
+                if i % 32 == 31 {
+                    ui.end_row();
+                }
+            }
+        });
+    });
+}
+
+fn knobs_per_frame(c: &mut Criterion) {
+    let ctx = Context::default();
+    let mut values: Vec<f32> = (0..KNOBS).map(|i| i as f32 / KNOBS as f32).collect();
+    mixer_frame(&ctx, &mut values);
+
+    c.bench_function("256 knobs per frame", |b| {
+        b.iter(|| mixer_frame(&ctx, &mut values));
+    });
+}
+
+fn paint_knobs(c: &mut Criterion) {
+    let ctx = Context::default();
+    let config = KnobConfig::new(KnobStyle::Dot)
+        .with_size(32.0)
+        .with_label("Send", LabelPosition::Bottom);
+
+    c.bench_function("paint 256 knobs", |b| {
+        b.iter(|| {
+            let _ = ctx.run_ui(RawInput::default(), |ui| {
+                let painter = ui.painter();
+                for i in 0..KNOBS {
+                    let min = pos2((i % 32) as f32 * 48.0, (i / 32) as f32 * 64.0);
+                    let rect = Rect::from_min_size(min, vec2(48.0, 64.0));
+                    KnobPainter::new(&config, 0.0, 1.0).paint(painter, rect, i as f32 / 256.0);
+                }
+            });
+        });
+    });
+}
+
+criterion_group!(benches, knobs_per_frame, paint_knobs);
+criterion_main!(benches);
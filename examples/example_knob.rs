@@ -1,10 +1,10 @@
-use eframe::egui;
-use egui_knob::{Knob, KnobStyle, LabelPosition};
+use egui_knob::egui;
+use egui_knob::{ArcPlacement, Knob, KnobStyle, LabelPosition};
 
 fn main() -> eframe::Result<()> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([600.0, 350.0])
+            .with_inner_size([600.0, 500.0])
             .with_title("Knob demo"),
         ..Default::default()
     };
@@ -18,10 +18,13 @@ fn main() -> eframe::Result<()> {
 
 struct KnobDemo {
     values: [f32; 6],
+    gain: f32,
+    flat: f32,
     show_bg_arc: bool,
     show_filled: bool,
     use_step: bool,
     logarithmic_scaling: bool,
+    outer_ring: bool,
     knob_color: egui::Color32,
     line_color: egui::Color32,
     text_color: egui::Color32,
@@ -31,10 +34,13 @@ impl Default for KnobDemo {
     fn default() -> Self {
         Self {
             values: [f32::NAN; 6],
+            gain: 0.7,
+            flat: 0.3,
             show_bg_arc: true,
             show_filled: true,
             use_step: false,
             logarithmic_scaling: false,
+            outer_ring: false,
             knob_color: egui::Color32::DARK_GRAY,
             line_color: egui::Color32::LIGHT_BLUE,
             text_color: egui::Color32::WHITE,
@@ -53,6 +59,10 @@ impl eframe::App for KnobDemo {
             ui.checkbox(&mut self.show_filled, "Filled segment");
             ui.checkbox(&mut self.use_step, "Step (0.02)");
             ui.checkbox(&mut self.logarithmic_scaling, "Logarithmic");
+            ui.checkbox(&mut self.outer_ring, "Outer ring");
+            if ui.button("Reset all").clicked() {
+                self.values = [0.5; 6];
+            }
         });
 
         ui.horizontal(|ui| {
@@ -78,30 +88,44 @@ impl eframe::App for KnobDemo {
                     ("Thick Stroke", KnobStyle::Wiper),
                     ("360° Sweep", KnobStyle::Wiper),
                     ("Multi-Turn", KnobStyle::Dot),
-                    ("Large Font", KnobStyle::Wiper),
+                    ("Skirted", KnobStyle::Skirted),
                 ]
                 .iter()
                 .enumerate()
                 {
                     ui.vertical(|ui| {
-                        let mut knob = Knob::new(&mut self.values[i], 0., 1., *config)
+                        let mut knob = Knob::new(&mut self.values[i], 0., 1., config.clone())
                             .with_label(*label, LabelPosition::Bottom)
                             .with_background_arc(self.show_bg_arc)
                             .with_show_filled_segments(self.show_filled)
                             .with_colors(self.knob_color, self.line_color, self.text_color)
-                            .with_step(self.use_step.then_some(0.02))
+                            .with_visual_scale(0.85)
                             .with_double_click_reset(0.5)
-                            .with_middle_scroll();
+                            .with_middle_scroll()
+                            .with_drag_value_popup(true)
+                            .with_drag_ghost(true)
+                            .with_change_pulse(0.6);
+
+                        if self.use_step {
+                            knob = knob.with_step_by(0.02);
+                        }
 
                         if self.logarithmic_scaling {
                             knob = knob.with_logarithmic_scaling();
                         }
 
+                        if self.outer_ring {
+                            knob = knob.with_arc_placement(ArcPlacement::Outside);
+                        }
+
                         if *label == "Wiper, Sweep" {
                             knob = knob.with_sweep_range(0.25, 0.75).with_size(50.0);
                         }
                         if *label == "Thick Stroke" {
-                            knob = knob.with_stroke_width(4.0).with_size(60.0);
+                            knob = knob
+                                .with_stroke_width(4.0)
+                                .with_size(60.0)
+                                .with_mesh_arcs(true);
                         }
                         if *label == "360° Sweep" {
                             knob = knob.with_sweep_range(0.5, 1.0);
@@ -109,8 +133,12 @@ impl eframe::App for KnobDemo {
                         if *label == "Multi-Turn" {
                             knob = knob.with_sweep_range(0.0, 2.5);
                         }
-                        if *label == "Large Font" {
-                            knob = knob.with_size(70.0).with_font_size(18.0);
+                        if *label == "Skirted" {
+                            knob = knob
+                                .with_size(70.0)
+                                .with_font_size(18.0)
+                                .with_tick_marks(11)
+                                .with_background_arc(false);
                         }
 
                         ui.add(knob);
@@ -124,5 +152,29 @@ impl eframe::App for KnobDemo {
 
         ui.add_space(10.0);
         ui.separator();
+
+        let time = ui.input(|i| i.time) as f32;
+        let level = self.gain * (0.5 + 0.5 * (time * 3.0).sin() * (time * 0.7).cos());
+        ui.horizontal(|ui| {
+            ui.add(
+                Knob::new(&mut self.gain, 0.0, 1.0, KnobStyle::Wiper)
+                    .with_size(60.0)
+                    .with_label("Gain", LabelPosition::Right)
+                    .with_colors(self.knob_color, self.line_color, self.text_color)
+                    .with_meter(level)
+                    .with_peak_hold(1.0, 0.5),
+            );
+
+            let visuals = ui.visuals().clone();
+            ui.add(
+                Knob::new(&mut self.flat, 0.0, 1.0, KnobStyle::Dot)
+                    .with_size(60.0)
+                    .with_label("Flat", LabelPosition::Top)
+                    .with_value_position(LabelPosition::Bottom)
+                    .with_value_font_size(10.0)
+                    .with_flat_preset(&visuals),
+            );
+        });
+        ui.ctx().request_repaint();
     }
 }
@@ -0,0 +1,133 @@
+use egui::{DragValue, Response, Ui, Widget};
+
+use crate::format;
+use crate::gesture::{ParamGestureHandler, ValueEdit};
+use crate::math;
+use crate::widget::Knob;
+
+/// A knob with an editable numeric field beneath it, both bound to the same value
+///
+/// Created with [`Knob::with_value_field`]. The knob gives coarse rotary control while the
+/// field allows dragging in fine steps or typing an exact number.
+pub struct KnobWithValueField<'a> {
+    knob: Knob<'a>,
+}
+
+impl<'a> Knob<'a> {
+    /// Adds an editable numeric field beneath the knob
+    ///
+    /// The field uses the knob's range, steps, unit and number of decimals, and reports its
+    /// edits to the same gesture and undo callbacks. The returned response covers both and
+    /// reports a change from either.
+    pub fn with_value_field(self) -> KnobWithValueField<'a> {
+        KnobWithValueField { knob: self }
+    }
+}
+
+impl Widget for KnobWithValueField<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Knob {
+            value,
+            min,
+            max,
+            config,
+            mut gestures,
+            learn,
+            mut undo,
+            animation,
+            touch,
+        } = self.knob;
+        let decimals = format::auto_decimals(min, max, config.step_fraction(min, max));
+        let speed = (max - min).abs() * config.drag_sensitivity;
+        let suffix = config
+            .unit
+            .as_ref()
+            .map(|unit| format!(" {unit}"))
+            .unwrap_or_default();
+        // The field always shows and edits the value in `min..=max`
+        let (normalized, logarithmic) = (config.normalized_value, config.logarithmic_scaling);
+        let to_plain = move |value: f32| {
+            if normalized {
+                math::denormalize(value, min, max, logarithmic)
+            } else {
+                value
+            }
+        };
+
+        // Typed and dragged field values land on the knob's steps, too
+        let snap = config.clone();
+
+        ui.vertical(|ui| {
+            let knob = ui.add(Knob {
+                value: &mut *value,
+                min,
+                max,
+                config,
+                gestures: gestures
+                    .as_mut()
+                    .map(|handler| Box::new(&mut **handler) as Box<dyn ParamGestureHandler>),
+                learn,
+                undo: undo
+                    .as_mut()
+                    .map(|on_edit| Box::new(on_edit) as Box<dyn FnMut(ValueEdit)>),
+                animation,
+                touch,
+            });
+            let before = to_plain(*value);
+            let field = ui.add_sized(
+                [knob.rect.width(), ui.spacing().interact_size.y],
+                DragValue::from_get_set(|set: Option<f64>| {
+                    if let Some(plain) = set {
+                        let plain = snap.snap_value(plain as f32, min, max);
+                        *value = if normalized {
+                            math::normalize(plain, min, max, logarithmic)
+                        } else {
+                            plain
+                        };
+                    }
+                    to_plain(*value) as f64
+                })
+                .range(min.min(max)..=min.max(max))
+                .speed(speed)
+                .fixed_decimals(decimals)
+                .suffix(suffix),
+            );
+            // Dragging or typing in the field is one edit from start to end
+            let start = ui.data(|d| d.get_temp::<f32>(field.id));
+            let begins = start.is_none() && (field.drag_started() || field.gained_focus());
+            let ends = start.is_some() && (field.drag_stopped() || field.lost_focus());
+            if begins {
+                ui.data_mut(|d| d.insert_temp(field.id, before));
+            } else if ends {
+                ui.data_mut(|d| d.remove::<f32>(field.id));
+            }
+            if let Some(gestures) = &mut gestures {
+                if begins {
+                    gestures.begin_edit();
+                }
+                if field.changed() {
+                    let plain = to_plain(*value);
+                    let normalized = math::normalize(plain, min, max, logarithmic);
+                    gestures.perform_edit(plain, normalized);
+                }
+                if ends {
+                    gestures.end_edit();
+                }
+            }
+            if let Some(on_edit) = &mut undo
+                && let Some(old_value) = start.filter(|_| ends)
+            {
+                let new_value = to_plain(*value);
+                if old_value != new_value {
+                    on_edit(ValueEdit {
+                        id: knob.id,
+                        old_value,
+                        new_value,
+                    });
+                }
+            }
+            knob | field
+        })
+        .inner
+    }
+}
@@ -1,47 +1,1126 @@
-use crate::style::{KnobColors, KnobStyle, LabelPosition};
+use std::fmt;
+use std::sync::Arc;
 
+use egui::{Align, Color32, Context, FontFamily, FontId, Id, Style, Ui, Visuals};
+
+use crate::format::{self, NumberLocale, TimeUnit};
+use crate::formatters::Formatter;
+use crate::math;
+use crate::style::{
+    ArcPlacement, DeltaReadout, InvokeGesture, KnobColors, KnobSize, KnobStyle, KnobTheme,
+    LabelPosition, NanFallback, OutOfRange, TooltipMode,
+};
+
+/// Converts a value into its display text
+pub(crate) type FormatFn = Arc<dyn Fn(f32) -> String + Send + Sync>;
+
+/// Converts typed text back into a value
+pub(crate) type ParseFn = Arc<dyn Fn(&str) -> Option<f32> + Send + Sync>;
+
+/// Hold and decay timing of the peak marker
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PeakHold {
+    pub(crate) hold_time: f32,
+    pub(crate) decay_rate: f32,
+}
+
+/// Look settings made on the knob itself, which an installed theme must not replace
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ThemeOverrides {
+    pub(crate) colors: bool,
+    pub(crate) stroke_width: bool,
+    pub(crate) visual_scale: bool,
+    pub(crate) outline: bool,
+    pub(crate) font: bool,
+    pub(crate) value_font: bool,
+}
+
+/// Where the knob diameter comes from
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Sizing {
+    /// Follows the interact size of the egui spacing
+    Spacing,
+    /// Set explicitly with `with_size`
+    Fixed,
+    /// Fraction of the available width, clamped to `min..=max`
+    Relative { fraction: f32, min: f32, max: f32 },
+}
+
+/// Appearance and behavior of a knob, independent of the value it is bound to
+///
+/// Build it once, e.g. from user settings, and pass it to
+/// [`Knob::from_config`](crate::Knob::from_config).
+/// The setters are the same as the builder methods of [`Knob`](crate::Knob).
+#[derive(Clone)]
 pub struct KnobConfig {
     pub(crate) size: f32,
+    pub(crate) sizing: Sizing,
+    pub(crate) visual_scale: f32,
     pub(crate) font_size: f32,
+    pub(crate) relative_font: Option<f32>,
+    pub(crate) font_family: FontFamily,
     pub(crate) stroke_width: f32,
     pub(crate) colors: KnobColors,
+    pub(crate) overrides: ThemeOverrides,
     pub(crate) label: Option<String>,
     pub(crate) label_position: LabelPosition,
+    pub(crate) value_position: Option<LabelPosition>,
+    pub(crate) show_value: bool,
+    pub(crate) fixed_width_value: bool,
+    pub(crate) widest_value: Option<String>,
+    pub(crate) tooltip_only_labels: bool,
+    pub(crate) label_min_size: Option<f32>,
+    pub(crate) tooltip: TooltipMode,
+    pub(crate) value_font_size: Option<f32>,
+    pub(crate) value_font_family: Option<FontFamily>,
     pub(crate) style: KnobStyle,
     pub(crate) label_offset: f32,
-    pub(crate) label_format: Box<dyn Fn(f32) -> String>,
+    pub(crate) knob_align: Align,
+    pub(crate) label_max_width: Option<f32>,
+    pub(crate) label_wrap_width: Option<f32>,
+    pub(crate) label_format: Option<FormatFn>,
+    pub(crate) auto_decimals: usize,
+    pub(crate) label_parse: Option<ParseFn>,
+    pub(crate) unit: Option<String>,
+    pub(crate) si_digits: Option<usize>,
+    pub(crate) locale: NumberLocale,
     pub(crate) step: Option<f32>,
+    pub(crate) value_step: Option<f32>,
     pub(crate) drag_sensitivity: f32,
+    pub(crate) show_outline: bool,
+    pub(crate) tick_count: usize,
     pub(crate) show_background_arc: bool,
     pub(crate) show_filled_segments: bool,
+    pub(crate) bipolar_fill: bool,
+    pub(crate) mesh_arcs: bool,
+    pub(crate) arc_segments: usize,
+    pub(crate) arc_placement: ArcPlacement,
+    pub(crate) peak_hold: Option<PeakHold>,
+    pub(crate) meter_level: Option<f32>,
+    pub(crate) automation: Option<f32>,
+    pub(crate) gauge_response_time: Option<f32>,
+    pub(crate) drag_value_popup: bool,
+    pub(crate) delta_readout: Option<DeltaReadout>,
+    pub(crate) show_drag_ghost: bool,
+    pub(crate) pulse_duration: Option<f32>,
     pub(crate) min_angle: f32,
     pub(crate) max_angle: f32,
-    pub(crate) reset_value: Option<f32>,
+    pub(crate) default_value: Option<f32>,
+    pub(crate) show_default_marker: bool,
+    pub(crate) spring_return: bool,
     pub(crate) allow_scroll: bool,
     pub(crate) logarithmic_scaling: bool,
+    pub(crate) normalized_value: bool,
+    pub(crate) out_of_range: OutOfRange,
+    pub(crate) nan_fallback: NanFallback,
+    pub(crate) id_salt: Option<Id>,
+    pub(crate) learn_armed: bool,
+    pub(crate) clipboard: bool,
+    pub(crate) value_editor: Option<InvokeGesture>,
+}
+
+impl fmt::Debug for KnobConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KnobConfig")
+            .field("size", &self.size)
+            .field("sizing", &self.sizing)
+            .field("visual_scale", &self.visual_scale)
+            .field("font_size", &self.font_size)
+            .field("relative_font", &self.relative_font)
+            .field("font_family", &self.font_family)
+            .field("stroke_width", &self.stroke_width)
+            .field("colors", &self.colors)
+            .field("overrides", &self.overrides)
+            .field("label", &self.label)
+            .field("label_position", &self.label_position)
+            .field("value_position", &self.value_position)
+            .field("show_value", &self.show_value)
+            .field("fixed_width_value", &self.fixed_width_value)
+            .field("widest_value", &self.widest_value)
+            .field("tooltip_only_labels", &self.tooltip_only_labels)
+            .field("label_min_size", &self.label_min_size)
+            .field("tooltip", &self.tooltip)
+            .field("value_font_size", &self.value_font_size)
+            .field("value_font_family", &self.value_font_family)
+            .field("style", &self.style)
+            .field("label_offset", &self.label_offset)
+            .field("knob_align", &self.knob_align)
+            .field("label_max_width", &self.label_max_width)
+            .field("label_wrap_width", &self.label_wrap_width)
+            .field(
+                "label_format",
+                &self.label_format.as_ref().map(|_| format_args!("<fn>")),
+            )
+            .field("auto_decimals", &self.auto_decimals)
+            .field(
+                "label_parse",
+                &self.label_parse.as_ref().map(|_| format_args!("<fn>")),
+            )
+            .field("unit", &self.unit)
+            .field("si_digits", &self.si_digits)
+            .field("locale", &self.locale)
+            .field("step", &self.step)
+            .field("value_step", &self.value_step)
+            .field("drag_sensitivity", &self.drag_sensitivity)
+            .field("show_outline", &self.show_outline)
+            .field("tick_count", &self.tick_count)
+            .field("show_background_arc", &self.show_background_arc)
+            .field("show_filled_segments", &self.show_filled_segments)
+            .field("bipolar_fill", &self.bipolar_fill)
+            .field("mesh_arcs", &self.mesh_arcs)
+            .field("arc_segments", &self.arc_segments)
+            .field("arc_placement", &self.arc_placement)
+            .field("peak_hold", &self.peak_hold)
+            .field("meter_level", &self.meter_level)
+            .field("automation", &self.automation)
+            .field("gauge_response_time", &self.gauge_response_time)
+            .field("drag_value_popup", &self.drag_value_popup)
+            .field("delta_readout", &self.delta_readout)
+            .field("show_drag_ghost", &self.show_drag_ghost)
+            .field("pulse_duration", &self.pulse_duration)
+            .field("min_angle", &self.min_angle)
+            .field("max_angle", &self.max_angle)
+            .field("default_value", &self.default_value)
+            .field("show_default_marker", &self.show_default_marker)
+            .field("spring_return", &self.spring_return)
+            .field("allow_scroll", &self.allow_scroll)
+            .field("logarithmic_scaling", &self.logarithmic_scaling)
+            .field("normalized_value", &self.normalized_value)
+            .field("out_of_range", &self.out_of_range)
+            .field("nan_fallback", &self.nan_fallback)
+            .field("id_salt", &self.id_salt)
+            .field("learn_armed", &self.learn_armed)
+            .field("clipboard", &self.clipboard)
+            .field("value_editor", &self.value_editor)
+            .finish()
+    }
+}
+
+/// A stored knob configuration to apply to many knobs
+///
+/// Keep it in the app state and apply it every frame with
+/// [`Knob::with_preset`](crate::Knob::with_preset), instead of repeating the builder calls
+/// for each knob. Cloning is cheap: the configuration is shared.
+#[derive(Debug, Clone, Default)]
+pub struct KnobPreset {
+    config: Arc<KnobConfig>,
+}
+
+impl KnobPreset {
+    /// Stores `config` for reuse
+    pub fn new(config: KnobConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+
+    /// The stored configuration
+    pub fn config(&self) -> &KnobConfig {
+        &self.config
+    }
+}
+
+impl From<KnobConfig> for KnobPreset {
+    fn from(config: KnobConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+/// The visual settings of a knob, e.g. to persist user customizations
+///
+/// Serializable with the `serde` feature. Behavior, labels and formatting are left out.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct KnobAppearance {
+    /// Visual style of the indicator
+    pub style: KnobStyle,
+    /// Knob diameter, `None` to follow the egui spacing
+    pub size: Option<f32>,
+    /// Fraction of the knob's square that is painted, the rest is padding
+    pub visual_scale: f32,
+    /// Font size of the label
+    pub font_size: f32,
+    /// Width of the outline, arcs and indicator
+    pub stroke_width: f32,
+    /// Colors of the knob parts and labels
+    pub colors: KnobColors,
+    /// Where the label is drawn relative to the knob
+    pub label_position: LabelPosition,
+    /// Whether the outline circle is drawn
+    pub show_outline: bool,
+    /// Number of tick marks along the sweep, `0` for none
+    pub tick_count: usize,
+    /// Whether the arc showing the full range is drawn
+    pub show_background_arc: bool,
+    /// Whether the arc is filled up to the value
+    pub show_filled_segments: bool,
+    /// Whether the fill starts at the center of the sweep instead of the minimum
+    pub bipolar_fill: bool,
+    /// Whether the arcs are drawn inside the knob body or as a ring around it
+    pub arc_placement: ArcPlacement,
+    /// Start of the sweep in radians
+    pub min_angle: f32,
+    /// End of the sweep in radians
+    pub max_angle: f32,
+}
+
+impl Default for KnobAppearance {
+    fn default() -> Self {
+        KnobConfig::default().appearance()
+    }
+}
+
+impl Default for KnobConfig {
+    fn default() -> Self {
+        Self::new(KnobStyle::Wiper)
+    }
 }
 
 impl KnobConfig {
+    /// Creates the default configuration for a knob of the given style
     pub fn new(style: KnobStyle) -> Self {
         Self {
             size: 40.0,
+            sizing: Sizing::Spacing,
+            visual_scale: 1.0,
             font_size: 12.0,
+            relative_font: None,
+            font_family: FontFamily::Proportional,
             stroke_width: 2.0,
             colors: KnobColors::default(),
+            overrides: ThemeOverrides::default(),
             label: None,
             label_position: LabelPosition::Bottom,
+            value_position: None,
+            show_value: true,
+            fixed_width_value: false,
+            widest_value: None,
+            tooltip_only_labels: false,
+            label_min_size: None,
+            tooltip: TooltipMode::Value,
+            value_font_size: None,
+            value_font_family: None,
             style,
             label_offset: 1.0,
-            label_format: Box::new(|v| format!("{:.2}", v)),
+            knob_align: Align::Center,
+            label_max_width: None,
+            label_wrap_width: None,
+            label_format: None,
+            auto_decimals: 2,
+            label_parse: None,
+            unit: None,
+            si_digits: None,
+            locale: NumberLocale::PLAIN,
             step: None,
+            value_step: None,
             min_angle: -std::f32::consts::PI,
             max_angle: std::f32::consts::PI * 0.5,
             drag_sensitivity: 0.005,
+            show_outline: true,
+            tick_count: 0,
             show_background_arc: true,
             show_filled_segments: true,
-            reset_value: None,
-            allow_scroll:false,
+            bipolar_fill: false,
+            mesh_arcs: false,
+            arc_segments: 128,
+            arc_placement: ArcPlacement::Inside,
+            peak_hold: None,
+            meter_level: None,
+            automation: None,
+            gauge_response_time: None,
+            drag_value_popup: false,
+            delta_readout: None,
+            show_drag_ghost: false,
+            pulse_duration: None,
+            default_value: None,
+            show_default_marker: true,
+            spring_return: false,
+            allow_scroll: false,
             logarithmic_scaling: false,
+            normalized_value: false,
+            out_of_range: OutOfRange::default(),
+            nan_fallback: NanFallback::default(),
+            id_salt: None,
+            learn_armed: false,
+            clipboard: false,
+            value_editor: None,
+        }
+    }
+
+    /// Extracts the visual settings, e.g. to save them
+    pub fn appearance(&self) -> KnobAppearance {
+        KnobAppearance {
+            style: self.style.clone(),
+            size: match self.sizing {
+                Sizing::Fixed => Some(self.size),
+                Sizing::Spacing | Sizing::Relative { .. } => None,
+            },
+            visual_scale: self.visual_scale,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            colors: self.colors,
+            label_position: self.label_position,
+            show_outline: self.show_outline,
+            tick_count: self.tick_count,
+            show_background_arc: self.show_background_arc,
+            show_filled_segments: self.show_filled_segments,
+            bipolar_fill: self.bipolar_fill,
+            arc_placement: self.arc_placement,
+            min_angle: self.min_angle,
+            max_angle: self.max_angle,
+        }
+    }
+
+    /// Same as [`Knob::with_appearance`](crate::Knob::with_appearance)
+    pub fn with_appearance(mut self, appearance: KnobAppearance) -> Self {
+        if let Some(size) = appearance.size {
+            self = self.with_size(size);
+        }
+        self.style = appearance.style;
+        self.visual_scale = appearance.visual_scale;
+        self.font_size = appearance.font_size;
+        self.relative_font = None;
+        self.stroke_width = appearance.stroke_width;
+        self.colors = appearance.colors;
+        self.label_position = appearance.label_position;
+        self.show_outline = appearance.show_outline;
+        self.tick_count = appearance.tick_count;
+        self.show_background_arc = appearance.show_background_arc;
+        self.show_filled_segments = appearance.show_filled_segments;
+        self.bipolar_fill = appearance.bipolar_fill;
+        self.arc_placement = appearance.arc_placement;
+        self.min_angle = appearance.min_angle;
+        self.max_angle = appearance.max_angle;
+        self.overrides.colors = true;
+        self.overrides.stroke_width = true;
+        self.overrides.visual_scale = true;
+        self.overrides.outline = true;
+        self.overrides.font = true;
+        self
+    }
+
+    /// Sets the visual style of the knob indicator
+    pub fn with_style(mut self, style: KnobStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Same as [`Knob::with_sweep_range`](crate::Knob::with_sweep_range)
+    pub fn with_sweep_range(mut self, start_angle_normalized: f32, range: f32) -> Self {
+        if start_angle_normalized.is_nan() || range.is_nan() {
+            return self;
+        }
+
+        (self.min_angle, self.max_angle) = math::sweep_angles(start_angle_normalized, range);
+        self
+    }
+
+    /// Same as [`Knob::with_angle_range_degrees`](crate::Knob::with_angle_range_degrees)
+    pub fn with_angle_range_degrees(self, start_deg: f32, end_deg: f32) -> Self {
+        self.with_angle_range_radians(start_deg.to_radians(), end_deg.to_radians())
+    }
+
+    /// Same as [`Knob::with_angle_range_radians`](crate::Knob::with_angle_range_radians)
+    pub fn with_angle_range_radians(mut self, start: f32, end: f32) -> Self {
+        if !start.is_finite() || !end.is_finite() {
+            return self;
+        }
+
+        (self.min_angle, self.max_angle) = math::clock_sweep_angles(start, end);
+        self
+    }
+
+    /// Same as [`Knob::with_mirrored_sweep`](crate::Knob::with_mirrored_sweep)
+    pub fn with_mirrored_sweep(mut self) -> Self {
+        let mirror = |angle: f32| std::f32::consts::PI - angle;
+        (self.min_angle, self.max_angle) = (mirror(self.min_angle), mirror(self.max_angle));
+        self
+    }
+
+    /// Same as [`Knob::with_size`](crate::Knob::with_size)
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self.sizing = Sizing::Fixed;
+        self
+    }
+
+    /// Same as [`Knob::with_size_preset`](crate::Knob::with_size_preset)
+    pub fn with_size_preset(self, preset: KnobSize) -> Self {
+        self.with_size(preset.diameter())
+            .with_stroke_width(preset.stroke_width())
+            .with_font_size(preset.font_size())
+    }
+
+    /// Same as [`Knob::with_relative_size`](crate::Knob::with_relative_size)
+    pub fn with_relative_size(mut self, fraction: f32, min: f32, max: f32) -> Self {
+        self.sizing = Sizing::Relative {
+            fraction,
+            min,
+            max: max.max(min),
+        };
+        self
+    }
+
+    /// Same as [`Knob::with_visual_scale`](crate::Knob::with_visual_scale)
+    pub fn with_visual_scale(mut self, scale: f32) -> Self {
+        self.visual_scale = scale.clamp(0.1, 1.0);
+        self.overrides.visual_scale = true;
+        self
+    }
+
+    /// Same as [`Knob::with_font_size`](crate::Knob::with_font_size)
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self.relative_font = None;
+        self.overrides.font = true;
+        self
+    }
+
+    /// Same as [`Knob::with_relative_font`](crate::Knob::with_relative_font)
+    pub fn with_relative_font(mut self, ratio: f32) -> Self {
+        self.relative_font = Some(ratio);
+        self.overrides.font = true;
+        self
+    }
+
+    /// Same as [`Knob::with_font`](crate::Knob::with_font)
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.font_size = font.size;
+        self.relative_font = None;
+        self.font_family = font.family;
+        self.overrides.font = true;
+        self
+    }
+
+    /// Same as [`Knob::with_stroke_width`](crate::Knob::with_stroke_width)
+    pub fn with_stroke_width(mut self, width: f32) -> Self {
+        self.stroke_width = width;
+        self.overrides.stroke_width = true;
+        self
+    }
+
+    /// Same as [`Knob::with_colors`](crate::Knob::with_colors)
+    pub fn with_colors(
+        mut self,
+        knob_color: Color32,
+        line_color: Color32,
+        text_color: Color32,
+    ) -> Self {
+        self.colors = KnobColors::from_base(knob_color, line_color, text_color);
+        self.overrides.colors = true;
+        self
+    }
+
+    /// Same as [`Knob::with_knob_colors`](crate::Knob::with_knob_colors)
+    pub fn with_knob_colors(mut self, colors: KnobColors) -> Self {
+        self.colors = colors;
+        self.overrides.colors = true;
+        self
+    }
+
+    /// Same as [`Knob::with_flat_preset`](crate::Knob::with_flat_preset)
+    pub fn with_flat_preset(mut self, visuals: &Visuals) -> Self {
+        self.style = KnobStyle::Dot;
+        self.stroke_width = 1.5;
+        self.show_outline = false;
+        self.show_background_arc = true;
+        self.colors = KnobColors {
+            cap_fill_color: Color32::TRANSPARENT,
+            ..KnobColors::from_visuals(visuals)
+        };
+        self.overrides.colors = true;
+        self.overrides.stroke_width = true;
+        self.overrides.outline = true;
+        self
+    }
+
+    /// Same as [`Knob::with_theme`](crate::Knob::with_theme)
+    pub fn with_theme(mut self, theme: &KnobTheme) -> Self {
+        self.colors = theme.colors;
+        self.stroke_width = theme.stroke_width;
+        self.visual_scale = theme.visual_scale.clamp(0.1, 1.0);
+        self.show_outline = theme.show_outline;
+        self = self.with_font(theme.font.clone());
+        self.value_font_size = theme.value_font.as_ref().map(|font| font.size);
+        self.value_font_family = theme.value_font.as_ref().map(|font| font.family.clone());
+        self.overrides.colors = true;
+        self.overrides.stroke_width = true;
+        self.overrides.visual_scale = true;
+        self.overrides.outline = true;
+        self.overrides.value_font = true;
+        self
+    }
+
+    /// Same as [`Knob::with_label`](crate::Knob::with_label)
+    pub fn with_label(mut self, label: impl Into<String>, position: LabelPosition) -> Self {
+        self.label = Some(label.into());
+        self.label_position = position;
+        self
+    }
+
+    /// Same as [`Knob::with_value_position`](crate::Knob::with_value_position)
+    pub fn with_value_position(mut self, position: LabelPosition) -> Self {
+        self.value_position = Some(position);
+        self
+    }
+
+    /// Same as [`Knob::with_value_font`](crate::Knob::with_value_font)
+    pub fn with_value_font(mut self, font: FontId) -> Self {
+        self.value_font_size = Some(font.size);
+        self.value_font_family = Some(font.family);
+        self.overrides.value_font = true;
+        self
+    }
+
+    /// Same as [`Knob::with_show_value`](crate::Knob::with_show_value)
+    pub fn with_show_value(mut self, enabled: bool) -> Self {
+        self.show_value = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_fixed_width_value`](crate::Knob::with_fixed_width_value)
+    pub fn with_fixed_width_value(mut self, enabled: bool) -> Self {
+        self.fixed_width_value = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_widest_value`](crate::Knob::with_widest_value)
+    pub fn with_widest_value(mut self, text: impl Into<String>) -> Self {
+        self.widest_value = Some(text.into());
+        self
+    }
+
+    /// Same as [`Knob::with_tooltip`](crate::Knob::with_tooltip)
+    pub fn with_tooltip(mut self, mode: TooltipMode) -> Self {
+        self.tooltip = mode;
+        self
+    }
+
+    /// Same as [`Knob::with_clipboard`](crate::Knob::with_clipboard)
+    pub fn with_clipboard(mut self, enabled: bool) -> Self {
+        self.clipboard = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_value_editor`](crate::Knob::with_value_editor)
+    pub fn with_value_editor(mut self, gesture: InvokeGesture) -> Self {
+        self.value_editor = Some(gesture);
+        self
+    }
+
+    /// Same as [`Knob::with_learn_armed`](crate::Knob::with_learn_armed)
+    pub fn with_learn_armed(mut self, armed: bool) -> Self {
+        self.learn_armed = armed;
+        self
+    }
+
+    /// Same as [`Knob::with_tooltip_only_labels`](crate::Knob::with_tooltip_only_labels)
+    pub fn with_tooltip_only_labels(mut self, enabled: bool) -> Self {
+        self.tooltip_only_labels = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_label_min_size`](crate::Knob::with_label_min_size)
+    pub fn with_label_min_size(mut self, min_size: f32) -> Self {
+        self.label_min_size = Some(min_size);
+        self
+    }
+
+    /// Same as [`Knob::with_value_font_size`](crate::Knob::with_value_font_size)
+    pub fn with_value_font_size(mut self, size: f32) -> Self {
+        self.value_font_size = Some(size);
+        self.overrides.value_font = true;
+        self
+    }
+
+    /// Same as [`Knob::with_label_max_width`](crate::Knob::with_label_max_width)
+    pub fn with_label_max_width(mut self, max_width: f32) -> Self {
+        self.label_max_width = Some(max_width);
+        self
+    }
+
+    /// Same as [`Knob::with_label_wrap_width`](crate::Knob::with_label_wrap_width)
+    pub fn with_label_wrap_width(mut self, wrap_width: f32) -> Self {
+        self.label_wrap_width = Some(wrap_width);
+        self
+    }
+
+    /// Same as [`Knob::with_knob_align`](crate::Knob::with_knob_align)
+    pub fn with_knob_align(mut self, align: Align) -> Self {
+        self.knob_align = align;
+        self
+    }
+
+    /// Same as [`Knob::with_label_offset`](crate::Knob::with_label_offset)
+    pub fn with_label_offset(mut self, offset: f32) -> Self {
+        self.label_offset = offset;
+        self
+    }
+
+    /// Same as [`Knob::with_label_format`](crate::Knob::with_label_format)
+    pub fn with_label_format(
+        mut self,
+        format: impl Fn(f32) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.label_format = Some(Arc::new(format));
+        self.si_digits = None;
+        self
+    }
+
+    /// Same as [`Knob::with_custom_parser`](crate::Knob::with_custom_parser)
+    pub fn with_custom_parser(
+        mut self,
+        parse: impl Fn(&str) -> Option<f32> + Send + Sync + 'static,
+    ) -> Self {
+        self.label_parse = Some(Arc::new(parse));
+        self
+    }
+
+    /// Same as [`Knob::with_formatter`](crate::Knob::with_formatter)
+    pub fn with_formatter(mut self, formatter: Formatter) -> Self {
+        self.label_format = Some(formatter.format);
+        self.label_parse = Some(formatter.parse);
+        self.si_digits = None;
+        self
+    }
+
+    /// Same as [`Knob::with_note_format`](crate::Knob::with_note_format)
+    pub fn with_note_format(self, show_cents: bool) -> Self {
+        self.with_label_format(move |v| format::note_name(v, show_cents))
+    }
+
+    /// Same as [`Knob::with_time_format`](crate::Knob::with_time_format)
+    pub fn with_time_format(self, unit: TimeUnit) -> Self {
+        self.with_label_format(move |v| format::time(v, unit))
+    }
+
+    /// Same as [`Knob::with_unit`](crate::Knob::with_unit)
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Same as [`Knob::with_si_format`](crate::Knob::with_si_format)
+    pub fn with_si_format(mut self, significant_digits: usize) -> Self {
+        self.si_digits = Some(significant_digits);
+        self
+    }
+
+    /// Same as [`Knob::with_number_locale`](crate::Knob::with_number_locale)
+    pub fn with_number_locale(mut self, locale: NumberLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Same as [`Knob::with_step`](crate::Knob::with_step)
+    #[deprecated(
+        since = "0.4.0",
+        note = "use `with_step_by` with a step in value units"
+    )]
+    pub fn with_step(mut self, step: Option<f32>) -> Self {
+        self.step = step;
+        self.value_step = None;
+        self
+    }
+
+    /// Same as [`Knob::with_step_by`](crate::Knob::with_step_by)
+    pub fn with_step_by(mut self, step: f32) -> Self {
+        self.value_step = (step.is_finite() && step > 0.0).then_some(step);
+        self.step = None;
+        self
+    }
+
+    /// Same as [`Knob::with_outline`](crate::Knob::with_outline)
+    pub fn with_outline(mut self, enabled: bool) -> Self {
+        self.show_outline = enabled;
+        self.overrides.outline = true;
+        self
+    }
+
+    /// Same as [`Knob::with_tick_marks`](crate::Knob::with_tick_marks)
+    pub fn with_tick_marks(mut self, count: usize) -> Self {
+        self.tick_count = count;
+        self
+    }
+
+    /// Same as [`Knob::with_background_arc`](crate::Knob::with_background_arc)
+    pub fn with_background_arc(mut self, enabled: bool) -> Self {
+        self.show_background_arc = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_show_filled_segments`](crate::Knob::with_show_filled_segments)
+    pub fn with_show_filled_segments(mut self, enabled: bool) -> Self {
+        self.show_filled_segments = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_bipolar_fill`](crate::Knob::with_bipolar_fill)
+    pub fn with_bipolar_fill(mut self, enabled: bool) -> Self {
+        self.bipolar_fill = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_mesh_arcs`](crate::Knob::with_mesh_arcs)
+    pub fn with_mesh_arcs(mut self, enabled: bool) -> Self {
+        self.mesh_arcs = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_arc_segments`](crate::Knob::with_arc_segments)
+    pub fn with_arc_segments(mut self, segments: usize) -> Self {
+        self.arc_segments = segments.max(1);
+        self
+    }
+
+    /// Same as [`Knob::with_arc_placement`](crate::Knob::with_arc_placement)
+    pub fn with_arc_placement(mut self, placement: ArcPlacement) -> Self {
+        self.arc_placement = placement;
+        self
+    }
+
+    /// Same as [`Knob::with_peak_hold`](crate::Knob::with_peak_hold)
+    pub fn with_peak_hold(mut self, hold_time: f32, decay_rate: f32) -> Self {
+        self.peak_hold = Some(PeakHold {
+            hold_time: hold_time.max(0.0),
+            decay_rate: decay_rate.max(0.0),
+        });
+        self
+    }
+
+    /// Same as [`Knob::with_meter`](crate::Knob::with_meter)
+    pub fn with_meter(mut self, level: f32) -> Self {
+        self.meter_level = Some(level);
+        self
+    }
+
+    /// Same as [`Knob::with_automation`](crate::Knob::with_automation)
+    pub fn with_automation(mut self, automated: Option<f32>) -> Self {
+        self.automation = automated;
+        self
+    }
+
+    /// Same as [`Knob::with_gauge_mode`](crate::Knob::with_gauge_mode)
+    pub fn with_gauge_mode(mut self, response_time: f32) -> Self {
+        self.gauge_response_time = Some(response_time.max(0.0));
+        self
+    }
+
+    /// Same as [`Knob::with_drag_value_popup`](crate::Knob::with_drag_value_popup)
+    pub fn with_drag_value_popup(mut self, enabled: bool) -> Self {
+        self.drag_value_popup = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_drag_delta`](crate::Knob::with_drag_delta)
+    pub fn with_drag_delta(mut self, readout: DeltaReadout) -> Self {
+        self.delta_readout = Some(readout);
+        self
+    }
+
+    /// Same as [`Knob::with_drag_ghost`](crate::Knob::with_drag_ghost)
+    pub fn with_drag_ghost(mut self, enabled: bool) -> Self {
+        self.show_drag_ghost = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_change_pulse`](crate::Knob::with_change_pulse)
+    pub fn with_change_pulse(mut self, duration: f32) -> Self {
+        self.pulse_duration = Some(duration);
+        self
+    }
+
+    /// Same as [`Knob::with_drag_sensitivity`](crate::Knob::with_drag_sensitivity)
+    pub fn with_drag_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.drag_sensitivity = sensitivity;
+        self
+    }
+
+    /// Same as [`Knob::with_default`](crate::Knob::with_default)
+    pub fn with_default(mut self, value: f32) -> Self {
+        self.default_value = Some(value);
+        self
+    }
+
+    /// Same as [`Knob::with_double_click_reset`](crate::Knob::with_double_click_reset)
+    pub fn with_double_click_reset(self, reset_value: f32) -> Self {
+        self.with_default(reset_value)
+    }
+
+    /// Same as [`Knob::with_default_marker`](crate::Knob::with_default_marker)
+    pub fn with_default_marker(mut self, enabled: bool) -> Self {
+        self.show_default_marker = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_spring_return`](crate::Knob::with_spring_return)
+    pub fn with_spring_return(mut self, enabled: bool) -> Self {
+        self.spring_return = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_middle_scroll`](crate::Knob::with_middle_scroll)
+    pub fn with_middle_scroll(mut self) -> Self {
+        self.allow_scroll = true;
+        self
+    }
+
+    /// Same as [`Knob::with_normalized_value`](crate::Knob::with_normalized_value)
+    pub fn with_normalized_value(mut self, enabled: bool) -> Self {
+        self.normalized_value = enabled;
+        self
+    }
+
+    /// Same as [`Knob::with_logarithmic_scaling`](crate::Knob::with_logarithmic_scaling)
+    pub fn with_logarithmic_scaling(mut self) -> Self {
+        self.logarithmic_scaling = true;
+        self
+    }
+
+    /// Same as [`Knob::with_out_of_range`](crate::Knob::with_out_of_range)
+    pub fn with_out_of_range(mut self, policy: OutOfRange) -> Self {
+        self.out_of_range = policy;
+        self
+    }
+
+    /// Same as [`Knob::with_nan_fallback`](crate::Knob::with_nan_fallback)
+    pub fn with_nan_fallback(mut self, fallback: NanFallback) -> Self {
+        self.nan_fallback = fallback;
+        self
+    }
+
+    /// Same as [`Knob::with_id_salt`](crate::Knob::with_id_salt)
+    pub fn with_id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(id_salt));
+        self
+    }
+
+    /// Id of the knob widget, from the id salt if one was set
+    pub(crate) fn widget_id(&self, ui: &Ui, auto_id: Id) -> Id {
+        self.id_salt
+            .map_or(auto_id, |id_salt| ui.id().with(id_salt))
+    }
+
+    /// Resolves the per-frame settings that depend on the range and on the `Ui`
+    ///
+    /// `available_width` is the width the knob may grow into with a relative size.
+    pub(crate) fn prepare(&mut self, ui: &Ui, available_width: f32, min: f32, max: f32) {
+        self.prepare_with(ui.ctx(), ui.style(), available_width, min, max);
+    }
+
+    /// Same as [`KnobConfig::prepare`], for painting outside of a `Ui`
+    pub(crate) fn prepare_with(
+        &mut self,
+        ctx: &Context,
+        style: &Style,
+        available_width: f32,
+        min: f32,
+        max: f32,
+    ) {
+        if let Some(theme) = KnobTheme::installed(ctx) {
+            self.apply_installed_theme(&theme);
+        } else if !self.overrides.colors {
+            self.colors = KnobColors::from_visuals(&style.visuals);
+        }
+        self.auto_decimals = format::auto_decimals(min, max, self.step_fraction(min, max));
+        match self.sizing {
+            Sizing::Spacing => self.size = style.spacing.interact_size.x,
+            Sizing::Fixed => {}
+            Sizing::Relative { fraction, min, max } => {
+                self.size = (available_width * fraction).clamp(min, max);
+            }
+        }
+        if self
+            .label_min_size
+            .is_some_and(|min_size| self.size < min_size)
+        {
+            self.tooltip_only_labels = true;
+        }
+    }
+
+    /// Takes the look from an installed theme, except for what was set on the knob
+    fn apply_installed_theme(&mut self, theme: &KnobTheme) {
+        let overrides = self.overrides;
+        if !overrides.colors {
+            self.colors = theme.colors;
+        }
+        if !overrides.stroke_width {
+            self.stroke_width = theme.stroke_width;
+        }
+        if !overrides.visual_scale {
+            self.visual_scale = theme.visual_scale.clamp(0.1, 1.0);
+        }
+        if !overrides.outline {
+            self.show_outline = theme.show_outline;
+        }
+        if !overrides.font {
+            self.font_size = theme.font.size;
+            self.font_family = theme.font.family.clone();
+        }
+        if !overrides.value_font {
+            self.value_font_size = theme.value_font.as_ref().map(|font| font.size);
+            self.value_font_family = theme.value_font.as_ref().map(|font| font.family.clone());
+        }
+    }
+
+    /// Maps a value to its normalized position along the sweep, honoring the scaling
+    pub(crate) fn normalize(&self, value: f32, min: f32, max: f32) -> f32 {
+        math::normalize(value, min, max, self.logarithmic_scaling)
+    }
+
+    /// Maps a normalized position along the sweep back to a value
+    pub(crate) fn denormalize(&self, raw: f32, min: f32, max: f32) -> f32 {
+        math::denormalize(raw, min, max, self.logarithmic_scaling)
+    }
+
+    /// Replaces a NaN value with the fallback, leaving other values alone
+    pub(crate) fn sanitize(&self, value: f32, min: f32, max: f32) -> f32 {
+        if !value.is_nan() {
+            return value;
+        }
+        let fallback = match self.nan_fallback {
+            NanFallback::Min => min,
+            NanFallback::Default => self.default_value.unwrap_or(min),
+            NanFallback::Value(value) => value,
+        };
+        if fallback.is_nan() {
+            min
+        } else {
+            math::clamp_to_range(fallback, min, max)
+        }
+    }
+
+    /// Snaps a value to the nearest whole number of value steps from `min`
+    pub(crate) fn snap_value(&self, value: f32, min: f32, max: f32) -> f32 {
+        match self.value_step {
+            Some(step) if step > 0.0 && value.is_finite() => {
+                let snapped = min + ((value - min) / step).round() * step;
+                math::clamp_to_range(snapped, min, max)
+            }
+            _ => value,
+        }
+    }
+
+    /// Snaps a normalized position to the nearest step, if the knob has one
+    pub(crate) fn snap_raw(&self, raw: f32, min: f32, max: f32) -> f32 {
+        match (self.value_step, self.step) {
+            (Some(_), _) => {
+                let value = self.snap_value(self.denormalize(raw, min, max), min, max);
+                self.normalize(value, min, max)
+            }
+            (None, Some(step)) if step > 0.0 => ((raw / step).round() * step).clamp(0.0, 1.0),
+            _ => raw,
+        }
+    }
+
+    /// Whether drags snap to steps
+    pub(crate) fn is_stepped(&self) -> bool {
+        self.value_step.is_some() || self.step.is_some()
+    }
+
+    /// Normalized change per point of pointer movement
+    ///
+    /// The deprecated normalized step doubles as the sensitivity, as it always did.
+    pub(crate) fn drag_speed(&self) -> f32 {
+        match (self.value_step, self.step) {
+            (None, Some(step)) => step,
+            _ => self.drag_sensitivity,
+        }
+    }
+
+    /// Moves a normalized position by `steps` whole steps, e.g. for arrow keys
+    ///
+    /// Without a step the position moves by `fallback` per step.
+    pub(crate) fn step_raw(&self, raw: f32, steps: f32, min: f32, max: f32, fallback: f32) -> f32 {
+        match self.value_step {
+            Some(step) => {
+                let value = self.snap_value(self.denormalize(raw, min, max), min, max);
+                let value = math::clamp_to_range(value + steps * step, min, max);
+                self.normalize(value, min, max)
+            }
+            None => (raw + steps * self.step.unwrap_or(fallback)).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Step as a fraction of the range, `None` without a step
+    pub(crate) fn step_fraction(&self, min: f32, max: f32) -> Option<f32> {
+        match self.value_step {
+            Some(step) => Some(step / (max - min).abs()),
+            None => self.step,
+        }
+    }
+
+    /// Size of the name font, following the knob size when set relative to it
+    fn label_font_size(&self) -> f32 {
+        self.relative_font
+            .map_or(self.font_size, |ratio| self.size * ratio)
+    }
+
+    /// Font of the name label
+    pub(crate) fn name_font(&self) -> FontId {
+        FontId::new(self.label_font_size(), self.font_family.clone())
+    }
+
+    /// Font of the value label, falling back to the name font
+    pub(crate) fn value_font(&self) -> FontId {
+        FontId::new(
+            self.value_font_size
+                .unwrap_or_else(|| self.label_font_size()),
+            self.value_font_family
+                .clone()
+                .unwrap_or_else(|| self.font_family.clone()),
+        )
+    }
+
+    /// Formats a value for display, including the unit
+    pub(crate) fn format_value(&self, value: f32) -> String {
+        let (text, prefix) = self.format_parts(value);
+        match &self.unit {
+            Some(unit) => format!("{text} {prefix}{unit}"),
+            None => format!("{text}{prefix}"),
+        }
+    }
+
+    /// Formats a value for the value editor, which shows the unit next to the text
+    pub(crate) fn format_editable(&self, value: f32) -> String {
+        let (text, prefix) = self.format_parts(value);
+        format!("{text}{prefix}")
+    }
+
+    /// Formatted number and SI prefix of a value
+    fn format_parts(&self, value: f32) -> (String, &'static str) {
+        match self.si_digits {
+            Some(digits) => {
+                let (number, prefix) = format::si_prefixed(value, digits);
+                (self.locale.localize(&number), prefix)
+            }
+            None => match &self.label_format {
+                Some(label_format) => (label_format(value), ""),
+                None => (
+                    self.locale
+                        .localize(&format!("{:.*}", self.auto_decimals, value)),
+                    "",
+                ),
+            },
+        }
+    }
+
+    /// Parses text typed by the user back into a value
+    pub(crate) fn parse_value(&self, text: &str) -> Option<f32> {
+        match &self.label_parse {
+            Some(label_parse) => label_parse(text),
+            None => format::parse_number(&self.locale.delocalize(text), self.unit.as_deref()),
+        }
+    }
+
+    /// Formats a value change with an explicit sign, e.g. `+3.20 dB`
+    pub(crate) fn format_delta(&self, delta: f32) -> String {
+        let text = self.format_value(delta);
+        if delta >= 0.0 && !text.starts_with(['+', '-']) {
+            format!("+{text}")
+        } else {
+            text
         }
     }
 }
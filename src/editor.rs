@@ -0,0 +1,90 @@
+use egui::text_selection::CCursorRange;
+use egui::{Align2, Area, Frame, Id, Key, Order, Rect, TextEdit, Ui};
+
+use crate::config::KnobConfig;
+
+/// Width of the text field in the value editor
+const FIELD_WIDTH: f32 = 72.0;
+
+/// What the user did with the value editor this frame
+pub(crate) enum EditorOutcome {
+    /// Still editing
+    Open,
+    /// Enter was pressed on text that parses to this value
+    Commit(f32),
+    /// Closed without changing the value, with Escape or by focusing something else
+    Cancel,
+}
+
+/// Shows the pop-up value editor of the knob with `id` below `anchor`
+///
+/// The editor is drawn on the foreground layer, so it isn't clipped by the knob's parent.
+/// `opening` focuses the field and selects its text, on the frame the editor opens.
+pub(crate) fn show(
+    ui: &Ui,
+    id: Id,
+    anchor: Rect,
+    text: &mut String,
+    opening: bool,
+    config: &KnobConfig,
+    (min, max): (f32, f32),
+) -> EditorOutcome {
+    let area_id = id.with("value_editor");
+    let field_id = area_id.with("field");
+    let area = Area::new(area_id)
+        .order(Order::Foreground)
+        .fixed_pos(anchor.center_bottom())
+        .pivot(Align2::CENTER_TOP)
+        .constrain(true)
+        .show(ui.ctx(), |ui| {
+            Frame::popup(ui.style())
+                .show(ui, |ui| {
+                    let field = ui
+                        .horizontal(|ui| {
+                            let field = TextEdit::singleline(text)
+                                .id(field_id)
+                                .desired_width(FIELD_WIDTH)
+                                .show(ui);
+                            if let Some(unit) = &config.unit {
+                                ui.label(unit);
+                            }
+                            field
+                        })
+                        .inner;
+                    ui.weak(format!(
+                        "{} to {}",
+                        config.format_value(min.min(max)),
+                        config.format_value(min.max(max))
+                    ));
+                    field
+                })
+                .inner
+        });
+
+    let mut field = area.inner;
+    let response = &field.response.response;
+    if opening {
+        response.request_focus();
+        field
+            .state
+            .cursor
+            .set_char_range(Some(CCursorRange::select_all(&field.galley)));
+        field.state.store(ui.ctx(), field_id);
+        return EditorOutcome::Open;
+    }
+
+    if response.has_focus() {
+        EditorOutcome::Open
+    } else if ui.input(|i| i.key_pressed(Key::Enter)) {
+        match config.parse_value(text) {
+            Some(value) if !value.is_nan() => EditorOutcome::Commit(value),
+            // Keep editing text that isn't understood
+            _ => {
+                response.request_focus();
+                EditorOutcome::Open
+            }
+        }
+    } else {
+        EditorOutcome::Cancel
+    }
+}
@@ -0,0 +1,230 @@
+/// SI prefixes by power of 1000, from nano to giga
+const SI_PREFIXES: [&str; 7] = ["n", "µ", "m", "", "k", "M", "G"];
+const SI_OFFSET: i32 = 3;
+
+/// Scales `value` to an SI prefix and rounds it to `significant_digits`
+///
+/// Returns the number and the prefix separately, so the prefix can be attached to a unit:
+/// `1500.0` becomes `("1.50", "k")` with three significant digits.
+pub(crate) fn si_prefixed(value: f32, significant_digits: usize) -> (String, &'static str) {
+    if !value.is_finite() || value == 0.0 {
+        return (format!("{value}"), "");
+    }
+
+    let value = value as f64;
+    let digits = significant_digits.max(1);
+    let mut exponent = ((value.abs().log10() / 3.0).floor() as i32).clamp(-SI_OFFSET, SI_OFFSET);
+
+    loop {
+        let scaled = value / 1000f64.powi(exponent);
+        let integer_digits = (scaled.abs().log10().floor() as i32 + 1).max(1) as usize;
+        let decimals = digits.saturating_sub(integer_digits);
+        let text = format!("{scaled:.decimals$}");
+
+        // Rounding may carry over into the next prefix, e.g. 999.96 -> "1000"
+        let rounded: f64 = text.parse().unwrap_or(scaled);
+        if rounded.abs() >= 1000.0 && exponent < SI_OFFSET {
+            exponent += 1;
+            continue;
+        }
+
+        return (text, SI_PREFIXES[(exponent + SI_OFFSET) as usize]);
+    }
+}
+
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// Formats a MIDI note number as note name and octave, e.g. `60.0` as `"C4"`
+///
+/// With `show_cents` the deviation from the nearest note is appended, as in `"A4 +12¢"`.
+pub(crate) fn note_name(value: f32, show_cents: bool) -> String {
+    if !value.is_finite() {
+        return format!("{value}");
+    }
+
+    let note = value.round();
+    let name = NOTE_NAMES[(note as i32).rem_euclid(12) as usize];
+    let octave = (note / 12.0).floor() as i32 - 1;
+    let cents = ((value - note) * 100.0).round() as i32;
+
+    if show_cents && cents != 0 {
+        format!("{name}{octave} {cents:+}¢")
+    } else {
+        format!("{name}{octave}")
+    }
+}
+
+/// Unit of the value bound to a knob using the time format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeUnit {
+    /// Value is in seconds
+    Seconds,
+    /// Value is in milliseconds
+    Milliseconds,
+}
+
+/// Formats a duration picking µs, ms or s, e.g. `0.25` seconds as `"250 ms"`
+pub(crate) fn time(value: f32, unit: TimeUnit) -> String {
+    let seconds = match unit {
+        TimeUnit::Seconds => value,
+        TimeUnit::Milliseconds => value / 1000.0,
+    };
+
+    if !seconds.is_finite() {
+        return format!("{value}");
+    }
+
+    let magnitude = seconds.abs();
+    if magnitude >= 1.0 {
+        format!("{} s", three_digits(seconds))
+    } else if magnitude >= 1e-3 {
+        format!("{} ms", three_digits(seconds * 1e3))
+    } else if magnitude > 0.0 {
+        format!("{} µs", three_digits(seconds * 1e6))
+    } else {
+        "0 ms".to_owned()
+    }
+}
+
+/// Rounds to about three significant digits without dropping integer digits
+fn three_digits(value: f32) -> String {
+    let decimals = match value.abs() {
+        m if m < 10.0 => 2,
+        m if m < 100.0 => 1,
+        _ => 0,
+    };
+    format!("{value:.decimals$}")
+}
+
+/// Number of decimals needed to tell apart values one step apart
+///
+/// `step` is a fraction of the range, like the knob's step. Without a step, about a
+/// hundredth of the range is resolved.
+pub(crate) fn auto_decimals(min: f32, max: f32, step: Option<f32>) -> usize {
+    let span = (max - min).abs();
+    let resolution = match step {
+        Some(step) if step > 0.0 => step * span,
+        _ => span / 100.0,
+    };
+
+    if !resolution.is_finite() || resolution <= 0.0 {
+        return 2;
+    }
+
+    // Small tolerance so that e.g. a resolution of 0.01 gives 2 and not 3 decimals
+    (-resolution.log10() - 1e-4).ceil().clamp(0.0, 6.0) as usize
+}
+
+/// Decimal and digit grouping separators used when displaying and parsing numbers
+///
+/// The default uses a `.` decimal point without grouping, as Rust formats numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumberLocale {
+    /// Separates the integer from the fractional digits
+    pub decimal_separator: char,
+    /// Separates groups of three integer digits, if any
+    pub group_separator: Option<char>,
+}
+
+impl NumberLocale {
+    /// `1234.5`
+    pub const PLAIN: Self = Self::new('.', None);
+    /// `1,234.5`
+    pub const ENGLISH: Self = Self::new('.', Some(','));
+    /// `1.234,5`, as used in much of continental Europe
+    pub const EUROPEAN: Self = Self::new(',', Some('.'));
+
+    /// Creates a locale from its separators
+    ///
+    /// The default locale, [`NumberLocale::PLAIN`], is `new('.', None)`.
+    ///
+    /// # Arguments
+    /// * `decimal_separator` - Separates the integer from the fractional digits
+    /// * `group_separator` - Separates groups of three integer digits, `None` for no grouping
+    pub const fn new(decimal_separator: char, group_separator: Option<char>) -> Self {
+        Self {
+            decimal_separator,
+            group_separator,
+        }
+    }
+
+    /// Rewrites a number formatted by Rust, e.g. `"-1234.50"`, with these separators
+    pub(crate) fn localize(&self, number: &str) -> String {
+        if *self == Self::PLAIN {
+            return number.to_owned();
+        }
+
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (number, None),
+        };
+        let digits_start = integer
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(integer.len());
+        let (sign, digits) = integer.split_at(digits_start);
+
+        let mut text = String::from(sign);
+        for (i, digit) in digits.chars().enumerate() {
+            if let Some(group_separator) = self.group_separator
+                && i > 0
+                && (digits.len() - i) % 3 == 0
+            {
+                text.push(group_separator);
+            }
+            text.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            text.push(self.decimal_separator);
+            text.push_str(fraction);
+        }
+        text
+    }
+
+    /// Turns typed text back into Rust's number syntax, dropping group separators
+    pub(crate) fn delocalize(&self, text: &str) -> String {
+        text.chars()
+            .filter(|c| Some(*c) != self.group_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect()
+    }
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self::PLAIN
+    }
+}
+
+/// Parses a number with an optional SI prefix and unit, e.g. `"2k"`, `"-6 dB"` or `"1.5 kHz"`
+///
+/// `unit` is stripped from the end when present.
+pub(crate) fn parse_number(text: &str, unit: Option<&str>) -> Option<f32> {
+    let mut text = text.trim();
+    if let Some(unit) = unit.filter(|unit| !unit.is_empty()) {
+        text = text.strip_suffix(unit).unwrap_or(text).trim_end();
+    }
+
+    if let Ok(value) = text.parse::<f32>() {
+        return Some(value);
+    }
+
+    let prefix = text.chars().next_back()?;
+    let exponent = match prefix {
+        'n' => -3,
+        'µ' | 'u' => -2,
+        'm' => -1,
+        'k' | 'K' => 1,
+        'M' => 2,
+        'G' => 3,
+        _ => return None,
+    };
+    let number = text[..text.len() - prefix.len_utf8()].trim_end();
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|value| (value * 1000f64.powi(exponent)) as f32)
+}
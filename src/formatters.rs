@@ -0,0 +1,144 @@
+//! Ready-made value formats for common audio parameters
+//!
+//! Each function returns a [`Formatter`] that displays the value and parses typed text
+//! back, for use with [`Knob::with_formatter`](crate::Knob::with_formatter).
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::config::{FormatFn, ParseFn};
+use crate::format::{self, TimeUnit};
+
+/// A value format together with the parser that reads it back
+#[derive(Clone)]
+pub struct Formatter {
+    pub(crate) format: FormatFn,
+    pub(crate) parse: ParseFn,
+}
+
+impl Formatter {
+    /// Creates a formatter from a format function and its inverse
+    pub fn new(
+        format: impl Fn(f32) -> String + Send + Sync + 'static,
+        parse: impl Fn(&str) -> Option<f32> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            format: Arc::new(format),
+            parse: Arc::new(parse),
+        }
+    }
+
+    /// Formats a value for display
+    pub fn format(&self, value: f32) -> String {
+        (self.format)(value)
+    }
+
+    /// Parses text back into a value, `None` if the text is not understood
+    pub fn parse(&self, text: &str) -> Option<f32> {
+        (self.parse)(text)
+    }
+}
+
+impl fmt::Debug for Formatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Formatter").finish_non_exhaustive()
+    }
+}
+
+/// Strips `suffix` from the end of `text`, ignoring ASCII case and surrounding whitespace
+fn strip_unit<'t>(text: &'t str, suffix: &str) -> &'t str {
+    let text = text.trim();
+    match text.len().checked_sub(suffix.len()) {
+        Some(split)
+            if text.is_char_boundary(split) && text[split..].eq_ignore_ascii_case(suffix) =>
+        {
+            text[..split].trim_end()
+        }
+        _ => text,
+    }
+}
+
+/// Parses a number, accepting `inf` and `∞` for infinity
+fn parse_float(text: &str) -> Option<f32> {
+    match text.trim() {
+        "∞" | "+∞" => Some(f32::INFINITY),
+        "-∞" => Some(f32::NEG_INFINITY),
+        text => text.parse().ok(),
+    }
+}
+
+/// Decibels with one decimal, e.g. `"-6.0 dB"`, and `"-inf dB"` for silence
+pub fn db() -> Formatter {
+    Formatter::new(
+        |v| {
+            if v == f32::NEG_INFINITY {
+                "-inf dB".to_owned()
+            } else {
+                format!("{v:.1} dB")
+            }
+        },
+        |s| parse_float(strip_unit(s, "dB")),
+    )
+}
+
+/// Frequency with an SI prefix, e.g. `"440 Hz"` or `"1.50 kHz"`
+///
+/// Parses `"2k"` and `"2 kHz"` alike.
+pub fn hz() -> Formatter {
+    Formatter::new(
+        |v| {
+            let (number, prefix) = format::si_prefixed(v, 3);
+            format!("{number} {prefix}Hz")
+        },
+        |s| format::parse_number(strip_unit(s, "Hz"), None),
+    )
+}
+
+/// A fraction in `0.0..=1.0` as whole percent, e.g. `0.5` as `"50%"`
+pub fn percent() -> Formatter {
+    Formatter::new(
+        |v| format!("{:.0}%", v * 100.0),
+        |s| parse_float(strip_unit(s, "%")).map(|percent| percent / 100.0),
+    )
+}
+
+/// Signed semitones with up to two decimals, e.g. `"+7 st"` or `"-0.5 st"`
+pub fn semitones() -> Formatter {
+    Formatter::new(
+        // Adding zero turns -0.0 into 0.0, so no value shows as "-0"
+        |v| format!("{:+} st", (v * 100.0).round() / 100.0 + 0.0),
+        |s| parse_float(strip_unit(s, "st")),
+    )
+}
+
+/// A duration in milliseconds, switching to µs or s where that reads better
+///
+/// Displays `1200.0` as `"1.20 s"`. Plain numbers are parsed as milliseconds, numbers with
+/// a unit like `"1.2 s"` or `"800 µs"` are converted.
+pub fn ms() -> Formatter {
+    Formatter::new(
+        |v| format::time(v, TimeUnit::Milliseconds),
+        |s| {
+            let seconds = strip_unit(s, "s");
+            if seconds.len() == s.trim().len() {
+                parse_float(s)
+            } else {
+                format::parse_number(seconds, None).map(|seconds| seconds * 1000.0)
+            }
+        },
+    )
+}
+
+/// A compression ratio with one decimal, e.g. `"4.0:1"`, and `"∞:1"` for limiting
+pub fn ratio() -> Formatter {
+    Formatter::new(
+        |v| {
+            if v == f32::INFINITY {
+                "∞:1".to_owned()
+            } else {
+                format!("{v:.1}:1")
+            }
+        },
+        |s| parse_float(strip_unit(s, ":1")),
+    )
+}
@@ -0,0 +1,73 @@
+use egui::Id;
+
+/// Receives the edits of a knob as host-automation gestures
+///
+/// Plugin hosts (VST3, CLAP, AU) expect every change of a parameter to be wrapped in a
+/// gesture: `begin_edit`, any number of `perform_edit`, then `end_edit`. A drag is one
+/// gesture from press to release. Discrete changes, like a scroll step or a double-click
+/// reset, are reported as a gesture of their own within the same frame.
+///
+/// ```
+/// use egui_knob::ParamGestureHandler;
+///
+/// struct Automation<'a> {
+///     events: &'a mut Vec<String>,
+/// }
+///
+/// impl ParamGestureHandler for Automation<'_> {
+///     fn begin_edit(&mut self) {
+///         self.events.push("begin".to_owned());
+///     }
+///
+///     fn perform_edit(&mut self, value: f32, _normalized: f32) {
+///         self.events.push(format!("set {value}"));
+///     }
+///
+///     fn end_edit(&mut self) {
+///         self.events.push("end".to_owned());
+///     }
+/// }
+/// ```
+pub trait ParamGestureHandler {
+    /// A gesture starts, before its first change
+    fn begin_edit(&mut self);
+
+    /// The value changed during the gesture
+    ///
+    /// # Arguments
+    /// * `value` - New value of the knob
+    /// * `normalized` - New value mapped to `0.0..=1.0` along the sweep
+    fn perform_edit(&mut self, value: f32, normalized: f32);
+
+    /// The gesture is over
+    fn end_edit(&mut self);
+}
+
+impl<T: ParamGestureHandler + ?Sized> ParamGestureHandler for &mut T {
+    fn begin_edit(&mut self) {
+        (**self).begin_edit();
+    }
+
+    fn perform_edit(&mut self, value: f32, normalized: f32) {
+        (**self).perform_edit(value, normalized);
+    }
+
+    fn end_edit(&mut self) {
+        (**self).end_edit();
+    }
+}
+
+/// A completed change of a knob's value, reported once per gesture for undo history
+///
+/// A drag is one edit from press to release, a burst of scroll steps is one edit once
+/// scrolling pauses. A double-click reset, a typed value and assistive technology each
+/// give an edit of their own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueEdit {
+    /// Id of the knob's response
+    pub id: Id,
+    /// Value before the gesture
+    pub old_value: f32,
+    /// Value after the gesture
+    pub new_value: f32,
+}
@@ -0,0 +1,200 @@
+use std::ops::RangeInclusive;
+
+use egui::{Grid, Id, Response, Ui};
+
+use crate::config::KnobConfig;
+use crate::style::{KnobSize, KnobStyle, LabelPosition};
+use crate::widget::Knob;
+
+/// Adjusts each knob of a group, e.g. to set colors or formatting
+type ConfigureFn<'a> = Box<dyn Fn(Knob<'_>) -> Knob<'_> + 'a>;
+
+/// A bank of equally sized knobs laid out in a row or grid
+///
+/// Saves repeating the same builder calls for every knob of a channel strip.
+///
+/// ```
+/// # fn demo(ui: &mut egui_knob::egui::Ui) {
+/// # let (mut low, mut mid, mut high) = (0.5, 0.5, 0.5);
+/// use egui_knob::{KnobGroup, KnobStyle};
+///
+/// let group = KnobGroup::new(
+///     [
+///         ("Low", &mut low, -12.0..=12.0),
+///         ("Mid", &mut mid, -12.0..=12.0),
+///         ("High", &mut high, -12.0..=12.0),
+///     ],
+///     KnobStyle::Wiper,
+/// )
+/// .with_config(|knob| knob.with_unit("dB").with_double_click_reset(0.0))
+/// .show(ui);
+///
+/// if let Some(index) = group.changed() {
+///     println!("band {index} changed");
+/// }
+/// # }
+/// ```
+pub struct KnobGroup<'a> {
+    entries: Vec<(String, &'a mut f32, RangeInclusive<f32>)>,
+    style: KnobStyle,
+    size: f32,
+    columns: Option<usize>,
+    label_position: LabelPosition,
+    id_salt: Id,
+    configure: Option<ConfigureFn<'a>>,
+}
+
+impl<'a> KnobGroup<'a> {
+    /// Creates a group from `(label, value, range)` entries
+    ///
+    /// # Arguments
+    /// * `entries` - Label, bound value and value range of each knob
+    /// * `style` - Visual style shared by all knobs
+    pub fn new<L: Into<String>>(
+        entries: impl IntoIterator<Item = (L, &'a mut f32, RangeInclusive<f32>)>,
+        style: KnobStyle,
+    ) -> Self {
+        Self {
+            entries: entries
+                .into_iter()
+                .map(|(label, value, range)| (label.into(), value, range))
+                .collect(),
+            style,
+            size: KnobSize::Medium.diameter(),
+            columns: None,
+            label_position: LabelPosition::Bottom,
+            id_salt: Id::new("egui_knob_group"),
+            configure: None,
+        }
+    }
+
+    /// Sets the diameter of every knob in the group
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Wraps the knobs into rows of `columns`, instead of a single row
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// Sets where the labels go relative to their knobs
+    pub fn with_label_position(mut self, position: LabelPosition) -> Self {
+        self.label_position = position;
+        self
+    }
+
+    /// Sets the id of the underlying grid, needed when several groups share a `Ui`
+    pub fn with_id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Id::new(id_salt);
+        self
+    }
+
+    /// Applies the same builder calls to every knob
+    pub fn with_config(mut self, configure: impl Fn(Knob<'_>) -> Knob<'_> + 'a) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+
+    /// Shows the knobs, returning the response of each
+    pub fn show(self, ui: &mut Ui) -> KnobGroupResponse {
+        let columns = self.columns.unwrap_or(self.entries.len()).max(1);
+        let mut knobs = Vec::with_capacity(self.entries.len());
+
+        let grid = Grid::new(self.id_salt).num_columns(columns).show(ui, |ui| {
+            for (i, (label, value, range)) in self.entries.into_iter().enumerate() {
+                let mut knob = Knob::new(value, *range.start(), *range.end(), self.style.clone())
+                    .with_size(self.size)
+                    .with_label(label, self.label_position);
+                if let Some(configure) = &self.configure {
+                    knob = configure(knob);
+                }
+                knobs.push(ui.add(knob));
+
+                if (i + 1) % columns == 0 {
+                    ui.end_row();
+                }
+            }
+        });
+
+        KnobGroupResponse {
+            response: grid.response,
+            knobs,
+        }
+    }
+}
+
+impl Knob<'_> {
+    /// Shows a row of identically configured knobs bound to the elements of `values`
+    ///
+    /// Handy for EQ band gains or step-sequencer levels. Use [`KnobGroup`] instead when the
+    /// knobs need their own labels or ranges.
+    ///
+    /// ```
+    /// # fn demo(ui: &mut egui_knob::egui::Ui) {
+    /// use egui_knob::{Knob, KnobConfig, KnobStyle};
+    ///
+    /// let mut steps = [0.5; 16];
+    /// let config = KnobConfig::new(KnobStyle::Dot).with_size(24.0);
+    /// for index in Knob::many(ui, &mut steps, 0.0..=1.0, &config).changed_indices() {
+    ///     println!("step {index} changed");
+    /// }
+    /// # }
+    /// ```
+    pub fn many(
+        ui: &mut Ui,
+        values: &mut [f32],
+        range: RangeInclusive<f32>,
+        config: &KnobConfig,
+    ) -> KnobGroupResponse {
+        let inner = ui.horizontal(|ui| {
+            values
+                .iter_mut()
+                .map(|value| {
+                    ui.add(Knob::from_config(
+                        value,
+                        *range.start(),
+                        *range.end(),
+                        config.clone(),
+                    ))
+                })
+                .collect()
+        });
+
+        KnobGroupResponse {
+            response: inner.response,
+            knobs: inner.inner,
+        }
+    }
+}
+
+/// What happened to a [`KnobGroup`] or [`Knob::many`] this frame
+#[derive(Debug, Clone)]
+pub struct KnobGroupResponse {
+    /// Response of the whole group
+    pub response: Response,
+    /// Response of each knob, in the order of the entries
+    pub knobs: Vec<Response>,
+}
+
+impl KnobGroupResponse {
+    /// Index of the first knob whose value changed this frame
+    pub fn changed(&self) -> Option<usize> {
+        self.knobs.iter().position(Response::changed)
+    }
+
+    /// Indices of all knobs whose value changed this frame
+    pub fn changed_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.knobs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, knob)| knob.changed().then_some(i))
+    }
+
+    /// Index of the knob being dragged, if any
+    pub fn dragged(&self) -> Option<usize> {
+        self.knobs.iter().position(Response::dragged)
+    }
+}
@@ -0,0 +1,244 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use std::ops::{Deref, DerefMut};
+
+use egui::{Response, Sense, Ui, Widget, WidgetInfo, lerp};
+
+use crate::config::KnobConfig;
+use crate::render::KnobRenderer;
+use crate::state::{JogGrab, JogState};
+use crate::style::{ArcPlacement, KnobStyle};
+
+/// How far the shuttle ring turns either way from 12 o'clock, in radians
+const SHUTTLE_ANGLE: f32 = 0.75 * PI;
+
+/// Speed below which an inertia spin stops, in turns per second
+const REST_VELOCITY: f32 = 0.01;
+
+/// An endless wheel for scrubbing and transport control, like the jog wheel of a deck
+///
+/// Dragging around the center turns the wheel, adding to an unbounded position counted in
+/// turns. [`JogWheel::show`] reports the rotation of each frame and the current speed.
+/// With [`JogWheel::with_inertia`] the wheel keeps spinning after release, and
+/// [`JogWheel::with_shuttle_ring`] adds a spring-loaded outer ring for shuttle speed.
+///
+/// ```
+/// # fn demo(ui: &mut egui_knob::egui::Ui) {
+/// use egui_knob::{JogWheel, KnobStyle};
+///
+/// let mut position = 0.0;
+/// let jog = JogWheel::new(&mut position, KnobStyle::Dot)
+///     .with_inertia(0.5)
+///     .with_shuttle_ring(true)
+///     .show(ui);
+/// let scrub_seconds = jog.delta * 2.0;
+/// let shuttle_speed = jog.shuttle * 8.0;
+/// # }
+/// ```
+pub struct JogWheel<'a> {
+    position: &'a mut f32,
+    config: KnobConfig,
+    inertia: Option<f32>,
+    shuttle_ring: bool,
+}
+
+impl<'a> JogWheel<'a> {
+    /// Creates a jog wheel
+    ///
+    /// # Arguments
+    /// * `position` - Rotation in turns, growing clockwise without bounds
+    /// * `style` - Visual style of the indicator
+    pub fn new(position: &'a mut f32, style: KnobStyle) -> Self {
+        Self {
+            position,
+            config: KnobConfig::new(style),
+            inertia: None,
+            shuttle_ring: false,
+        }
+    }
+
+    /// Sets the look of the wheel, e.g. its label, size and colors
+    ///
+    /// The sweep of `config` is replaced by a full turn from 12 o'clock, without an arc.
+    pub fn with_config(mut self, config: KnobConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Keeps the wheel spinning after it is released
+    ///
+    /// The spin slows down exponentially with a time constant of `time_constant` seconds.
+    pub fn with_inertia(mut self, time_constant: f32) -> Self {
+        self.inertia = (time_constant > 0.0).then_some(time_constant);
+        self
+    }
+
+    /// Adds a ring around the wheel that sets the shuttle speed
+    ///
+    /// Dragging along the ring deflects it up to 135° either way from 12 o'clock, reported
+    /// as [`JogResponse::shuttle`]. It springs back to the center when released.
+    pub fn with_shuttle_ring(mut self, enabled: bool) -> Self {
+        self.shuttle_ring = enabled;
+        self
+    }
+
+    /// Shows the wheel, returning its rotation and speed along with the response
+    pub fn show(self, ui: &mut Ui) -> JogResponse {
+        let mut config = self.config.with_angle_range_degrees(0.0, 360.0);
+        config.show_background_arc = false;
+        if self.shuttle_ring {
+            config.arc_placement = ArcPlacement::Outside;
+        }
+        config.prepare(ui, ui.available_width(), 0.0, 1.0);
+        if !self.position.is_finite() {
+            *self.position = 0.0;
+        }
+
+        let renderer = KnobRenderer::new(&config, *self.position, 0.0, 0.0, 1.0);
+        let layout = renderer.calculate_layout(ui.painter());
+        let adjusted_size = layout.size();
+        let (auto_id, rect) = ui.allocate_space(adjusted_size);
+        let mut response =
+            ui.interact(rect, config.widget_id(ui, auto_id), Sense::click_and_drag());
+        let rect = ui.layout().align_size_within_rect(adjusted_size, rect);
+        let knob_rect = layout.knob_rect(rect, config.knob_align);
+        let center = knob_rect.center();
+        let radius = config.size / 2.0 * config.visual_scale;
+
+        let dt = ui.input(|i| i.stable_dt).max(f32::EPSILON);
+        let mut state = JogState::load(ui.ctx(), response.id);
+        let pointer_angle = response
+            .interact_pointer_pos()
+            .map(|pointer| (pointer - center).angle());
+
+        if response.drag_started()
+            && let Some(pointer) = response.interact_pointer_pos()
+        {
+            let on_ring = self.shuttle_ring && pointer.distance(center) > radius;
+            state.grab = Some(if on_ring {
+                JogGrab::Shuttle
+            } else {
+                JogGrab::Wheel
+            });
+            state.angle = (pointer - center).angle();
+            state.velocity = 0.0;
+        }
+
+        let mut delta = 0.0;
+        if response.dragged() {
+            match (state.grab, pointer_angle) {
+                (Some(JogGrab::Wheel), Some(angle)) => {
+                    delta = wrap_angle(angle - state.angle) / TAU;
+                    state.angle = angle;
+                    state.velocity = lerp(state.velocity..=delta / dt, 0.5);
+                }
+                (Some(JogGrab::Shuttle), Some(angle)) => {
+                    // Past the end of the ring the shuttle stays at that end instead of
+                    // flipping to the other one at 6 o'clock
+                    let deflection = wrap_angle(angle + FRAC_PI_2) / SHUTTLE_ANGLE;
+                    state.shuttle = if deflection.abs() > 1.0 && state.shuttle != 0.0 {
+                        state.shuttle.signum()
+                    } else {
+                        deflection.clamp(-1.0, 1.0)
+                    };
+                }
+                _ => {}
+            }
+        } else {
+            if response.drag_stopped() {
+                state.grab = None;
+                state.shuttle = 0.0;
+            }
+            match self.inertia {
+                Some(time_constant) if state.velocity != 0.0 => {
+                    delta = state.velocity * dt;
+                    state.velocity *= (-dt / time_constant).exp();
+                    if state.velocity.abs() < REST_VELOCITY {
+                        state.velocity = 0.0;
+                    } else {
+                        ui.ctx().request_repaint();
+                    }
+                }
+                _ => state.velocity = 0.0,
+            }
+        }
+        state.store(ui.ctx(), response.id);
+
+        if delta != 0.0 {
+            *self.position += delta;
+            response.mark_changed();
+        }
+
+        let position = *self.position;
+        let renderer = KnobRenderer::new(&config, position, position.rem_euclid(1.0), 0.0, 1.0);
+        let painter = ui.painter();
+        renderer.render_knob(painter, center, radius, response.hovered());
+        if self.shuttle_ring {
+            let mut ring = config
+                .clone()
+                .with_angle_range_radians(-SHUTTLE_ANGLE, SHUTTLE_ANGLE);
+            ring.show_background_arc = true;
+            ring.show_filled_segments = true;
+            ring.bipolar_fill = true;
+            let raw = (state.shuttle + 1.0) / 2.0;
+            KnobRenderer::new(&ring, state.shuttle, raw, -1.0, 1.0)
+                .render_ring(painter, center, radius);
+        }
+        renderer.render_label(painter, rect, knob_rect);
+
+        let label = config.label.as_deref().unwrap_or_default();
+        response.widget_info(|| WidgetInfo::slider(ui.is_enabled(), position as f64, label));
+
+        JogResponse {
+            response,
+            delta,
+            velocity: state.velocity,
+            shuttle: state.shuttle,
+        }
+    }
+}
+
+impl Widget for JogWheel<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// Maps an angle difference into `-PI..PI`, the shortest way round
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// What happened to a [`JogWheel`] this frame
+///
+/// Derefs to the underlying [`Response`].
+#[derive(Debug, Clone)]
+pub struct JogResponse {
+    /// Response of the wheel widget
+    pub response: Response,
+    /// Rotation this frame in turns, positive clockwise
+    pub delta: f32,
+    /// Rotation speed in turns per second, positive clockwise, including an inertia spin
+    pub velocity: f32,
+    /// Deflection of the shuttle ring in `-1.0..=1.0`, `0.0` while released or without one
+    pub shuttle: f32,
+}
+
+impl Deref for JogResponse {
+    type Target = Response;
+
+    fn deref(&self) -> &Response {
+        &self.response
+    }
+}
+
+impl DerefMut for JogResponse {
+    fn deref_mut(&mut self) -> &mut Response {
+        &mut self.response
+    }
+}
+
+impl From<JogResponse> for Response {
+    fn from(response: JogResponse) -> Self {
+        response.response
+    }
+}
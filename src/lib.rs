@@ -1,9 +1,42 @@
+mod combo;
 mod config;
+mod editor;
+mod format;
+pub mod formatters;
+mod gesture;
+mod group;
+mod jog;
+pub mod math;
+mod painter;
+mod range;
 mod render;
+mod response;
+mod state;
+mod stereo;
 mod style;
+mod switch;
 mod widget;
 
+/// The egui version this crate is built against
+///
+/// Use these types instead of a separately declared `egui` dependency to avoid mismatches
+/// like "expected `ecolor::Color32`, found a different `Color32`" when the versions differ.
 pub use egui;
 
-pub use style::{KnobStyle, LabelPosition};
+pub use combo::KnobWithValueField;
+pub use config::{KnobAppearance, KnobConfig, KnobPreset};
+pub use format::{NumberLocale, TimeUnit};
+pub use gesture::{ParamGestureHandler, ValueEdit};
+pub use group::{KnobGroup, KnobGroupResponse};
+pub use jog::{JogResponse, JogWheel};
+pub use painter::{KnobPainter, paint_knob};
+pub use range::RangeKnob;
+pub use response::{ChangeCause, KnobResponse};
+pub use state::{KnobGeometry, KnobState};
+pub use stereo::{StereoKnobs, StereoLink, StereoResponse};
+pub use style::{
+    ArcPlacement, DeltaReadout, InvokeGesture, KnobColors, KnobIndicator, KnobSize, KnobStyle,
+    KnobTheme, LabelPosition, NanFallback, OutOfRange, TooltipContent, TooltipMode,
+};
+pub use switch::RotarySwitch;
 pub use widget::Knob;
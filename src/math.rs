@@ -0,0 +1,88 @@
+//! Angle and value mapping used by the knobs
+//!
+//! Custom overlays and painters can use these to line up with the widget. Angles are in
+//! radians as used by [`egui::Vec2::angled`], normalized positions run from `0.0` at the
+//! start of the sweep to `1.0` at its end.
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use egui::remap;
+
+/// Start and end angle of a sweep, see [`Knob::with_sweep_range`](crate::Knob::with_sweep_range)
+///
+/// # Arguments
+/// * `start_angle_normalized` - Starting position as fraction of full circle, `0.0` at the bottom
+/// * `range` - Sweep as fraction of full circle, negative ranges sweep counter-clockwise
+pub fn sweep_angles(start_angle_normalized: f32, range: f32) -> (f32, f32) {
+    let min_angle = start_angle_normalized.rem_euclid(1.0) * TAU + PI / 2.0;
+    (min_angle, min_angle + range * TAU)
+}
+
+/// Start and end angle of a sweep between two clock positions, see
+/// [`Knob::with_angle_range_radians`](crate::Knob::with_angle_range_radians)
+///
+/// # Arguments
+/// * `start` - Start of the sweep in radians, clockwise from 12 o'clock
+/// * `end` - End of the sweep in radians, clockwise from 12 o'clock. Ends before `start`
+///   sweep counter-clockwise
+pub fn clock_sweep_angles(start: f32, end: f32) -> (f32, f32) {
+    let min_angle = start.rem_euclid(TAU) - FRAC_PI_2;
+    (min_angle, min_angle + (end - start))
+}
+
+/// Angle of a normalized position along the sweep from `min_angle` to `max_angle`
+///
+/// `NaN` maps to the start of the sweep.
+pub fn angle_at(normalized: f32, min_angle: f32, max_angle: f32) -> f32 {
+    if normalized.is_nan() {
+        min_angle
+    } else {
+        min_angle + normalized * (max_angle - min_angle)
+    }
+}
+
+/// Normalized position of `angle` along the sweep, counted from `min_angle` in the
+/// direction of the sweep
+///
+/// Angles outside a sweep of less than a full turn map past `1.0`, not below `0.0`.
+pub fn normalized_at_angle(angle: f32, min_angle: f32, max_angle: f32) -> f32 {
+    let sweep = max_angle - min_angle;
+    ((angle - min_angle) * sweep.signum()).rem_euclid(TAU) / sweep.abs()
+}
+
+/// Shortest distance between two angles, in `0.0..=PI`
+pub fn angle_distance(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(TAU);
+    difference.min(TAU - difference)
+}
+
+/// Maps a value in `min..=max` to its normalized position
+///
+/// With `logarithmic`, the range is spread over one decade like
+/// [`Knob::with_logarithmic_scaling`](crate::Knob::with_logarithmic_scaling). An empty
+/// range maps every value to `0.0`.
+pub fn normalize(value: f32, min: f32, max: f32, logarithmic: bool) -> f32 {
+    if min == max {
+        0.0
+    } else if logarithmic {
+        remap(value, min..=max, 1.0..=10.0).log(10.0)
+    } else {
+        remap(value, min..=max, 0.0..=1.0)
+    }
+}
+
+/// Maps a normalized position back to a value in `min..=max`, the inverse of [`normalize`]
+pub fn denormalize(normalized: f32, min: f32, max: f32, logarithmic: bool) -> f32 {
+    if logarithmic {
+        remap(10f32.powf(normalized), 1.0..=10.0, min..=max)
+    } else {
+        remap(normalized, 0.0..=1.0, min..=max)
+    }
+}
+
+/// Clamps `value` to the range between `min` and `max` in either order
+///
+/// Unlike [`f32::clamp`] this doesn't panic on NaN bounds, which are ignored.
+pub(crate) fn clamp_to_range(value: f32, min: f32, max: f32) -> f32 {
+    value.max(min.min(max)).min(min.max(max))
+}
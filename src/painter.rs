@@ -0,0 +1,90 @@
+use egui::{Align2, Painter, Rect};
+
+use crate::config::KnobConfig;
+use crate::render::KnobRenderer;
+use crate::state::KnobGeometry;
+
+/// Paints knobs with a raw [`Painter`], without allocating space or handling input
+///
+/// For custom layers, plots or node-graph canvases that manage interaction themselves.
+/// Knob and labels are laid out as by [`Knob`](crate::Knob) and centered in the target rect.
+///
+/// ```
+/// # fn demo(painter: &egui_knob::egui::Painter, rect: egui_knob::egui::Rect) {
+/// use egui_knob::{KnobConfig, KnobPainter, KnobStyle};
+///
+/// let config = KnobConfig::new(KnobStyle::Dot).with_size(32.0);
+/// let geometry = KnobPainter::new(&config, 0.0, 1.0)
+///     .with_hovered(true)
+///     .paint(painter, rect, 0.25);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct KnobPainter<'a> {
+    config: &'a KnobConfig,
+    min: f32,
+    max: f32,
+    hovered: bool,
+}
+
+impl<'a> KnobPainter<'a> {
+    /// Creates a painter for knobs with the given config and range
+    ///
+    /// # Arguments
+    /// * `config` - Look and formatting of the knob
+    /// * `min` - Minimum value
+    /// * `max` - Maximum value
+    pub fn new(config: &'a KnobConfig, min: f32, max: f32) -> Self {
+        Self {
+            config,
+            min,
+            max,
+            hovered: false,
+        }
+    }
+
+    /// Draws the knob in its hover color
+    pub fn with_hovered(mut self, hovered: bool) -> Self {
+        self.hovered = hovered;
+        self
+    }
+
+    /// Paints the knob showing `value` in `rect`
+    ///
+    /// Returns where the knob was drawn, e.g. for hit-testing the caller's own input.
+    pub fn paint(&self, painter: &Painter, rect: Rect, value: f32) -> KnobGeometry {
+        let mut config = self.config.clone();
+        let ctx = painter.ctx();
+        config.prepare_with(ctx, &ctx.global_style(), rect.width(), self.min, self.max);
+
+        let value = config.sanitize(value, self.min, self.max);
+        let raw = config.normalize(value, self.min, self.max);
+        let renderer = KnobRenderer::new(&config, value, raw, self.min, self.max);
+        let layout = renderer.calculate_layout(painter);
+        let rect = Align2::CENTER_CENTER.align_size_within_rect(layout.size(), rect);
+        let knob_rect = layout.knob_rect(rect, config.knob_align);
+        let center = knob_rect.center();
+        let radius = config.size / 2.0 * config.visual_scale;
+
+        renderer.render_knob(painter, center, radius, self.hovered);
+        renderer.render_label(painter, rect, knob_rect);
+
+        KnobGeometry {
+            center,
+            radius,
+            angle: renderer.compute_angle(),
+        }
+    }
+}
+
+/// Paints a knob showing `value` in `rect`, see [`KnobPainter`]
+pub fn paint_knob(
+    painter: &Painter,
+    rect: Rect,
+    value: f32,
+    min: f32,
+    max: f32,
+    config: &KnobConfig,
+) -> KnobGeometry {
+    KnobPainter::new(config, min, max).paint(painter, rect, value)
+}
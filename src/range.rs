@@ -0,0 +1,182 @@
+use egui::{Response, Sense, Ui, Widget, WidgetInfo};
+
+use crate::math;
+use crate::render::{KnobRenderer, range_text};
+use crate::state::{RangeDrag, RangeHandle};
+use crate::style::TooltipMode;
+use crate::widget::Knob;
+
+/// Angular distance within which a drag grabs one end instead of the whole range
+const GRAB_ANGLE: f32 = 0.3;
+
+/// A knob bound to the two ends of a range, e.g. a key range or band-pass limits
+///
+/// Created with [`Knob::into_range`]. Both ends get an indicator with the filled arc
+/// between them. Dragging near an end moves that end, dragging between them moves both.
+pub struct RangeKnob<'a> {
+    knob: Knob<'a>,
+    low: &'a mut f32,
+}
+
+impl<'a> Knob<'a> {
+    /// Turns the knob into a range knob whose low end is bound to `low`
+    ///
+    /// The value passed to [`Knob::new`] becomes the high end. The look, label, value
+    /// format, scaling, steps and drag sensitivity carry over. A range knob is only
+    /// dragged, though, so these settings are ignored:
+    ///
+    /// - keyboard, scroll wheel and AccessKit input
+    /// - double-click reset, spring return and the value editor
+    /// - clipboard and context menu
+    /// - gesture, MIDI learn, undo and touch callbacks, and [`Knob::animate_to`]
+    ///
+    /// Assistive technology sees a slider at the high end, with both ends as its text.
+    pub fn into_range(self, low: &'a mut f32) -> RangeKnob<'a> {
+        RangeKnob { knob: self, low }
+    }
+}
+
+impl Widget for RangeKnob<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Knob {
+            value: high,
+            min,
+            max,
+            mut config,
+            ..
+        } = self.knob;
+        let low = self.low;
+        config.prepare(ui, ui.available_width(), min, max);
+
+        if low.is_nan() {
+            *low = min;
+        }
+        if high.is_nan() {
+            *high = max;
+        }
+        // Like a single knob, a range knob over an empty or non-finite range is parked
+        let inert = min == max || !(min.is_finite() && max.is_finite());
+        let (mut low_raw, mut high_raw) = if inert {
+            (0.0, 0.0)
+        } else {
+            (
+                config.normalize(*low, min, max),
+                config.normalize(*high, min, max),
+            )
+        };
+        if low_raw > high_raw {
+            std::mem::swap(&mut low_raw, &mut high_raw);
+            std::mem::swap(low, high);
+        }
+
+        let renderer =
+            KnobRenderer::new(&config, *high, high_raw, min, max).with_range_start(*low, low_raw);
+        let layout = renderer.calculate_layout(ui.painter());
+        let adjusted_size = layout.size();
+        let (auto_id, rect) = ui.allocate_space(adjusted_size);
+        let sense = if inert {
+            Sense::hover()
+        } else {
+            Sense::click_and_drag()
+        };
+        let mut response = ui.interact(rect, config.widget_id(ui, auto_id), sense);
+        let rect = ui.layout().align_size_within_rect(adjusted_size, rect);
+        let knob_rect = layout.knob_rect(rect, config.knob_align);
+        let center = knob_rect.center();
+        let radius = config.size / 2.0 * config.visual_scale;
+
+        let drag = RangeDrag::track(ui, &response, || {
+            let Some(pointer) = response.interact_pointer_pos() else {
+                return RangeHandle::Both;
+            };
+            let pointer_angle = (pointer - center).angle();
+            let distance_to = |raw: f32| {
+                math::angle_distance(
+                    pointer_angle,
+                    math::angle_at(raw, config.min_angle, config.max_angle),
+                )
+            };
+            let (to_low, to_high) = (distance_to(low_raw), distance_to(high_raw));
+            let t = math::normalized_at_angle(pointer_angle, config.min_angle, config.max_angle);
+
+            if to_low.min(to_high) > GRAB_ANGLE && t > low_raw && t < high_raw {
+                RangeHandle::Both
+            } else if to_low <= to_high {
+                RangeHandle::Low
+            } else {
+                RangeHandle::High
+            }
+        });
+
+        let before = (*low, *high);
+        if let Some(mut drag) = drag {
+            let delta = -response.drag_delta().y * config.drag_speed();
+            // Like a single knob, each end keeps what snapping rounded away, so slow drags
+            // still reach the next step
+            let stepped = config.is_stepped();
+            let step = |raw: f32, remainder: &mut f32, low: f32, high: f32| {
+                let moved = (raw + *remainder + delta).clamp(low, high);
+                let snapped = config.snap_raw(moved, min, max).clamp(low, high);
+                if stepped {
+                    *remainder = moved - snapped;
+                }
+                snapped
+            };
+
+            match drag.handle {
+                RangeHandle::Low => {
+                    low_raw = step(low_raw, &mut drag.low_remainder, 0.0, high_raw);
+                }
+                RangeHandle::High => {
+                    high_raw = step(high_raw, &mut drag.high_remainder, low_raw, 1.0);
+                }
+                RangeHandle::Both => {
+                    let (lowest, highest) = (0.0, 1.0 - (high_raw - low_raw));
+                    let moved = step(low_raw, &mut drag.low_remainder, lowest, highest) - low_raw;
+                    low_raw += moved;
+                    high_raw += moved;
+                }
+            }
+            drag.store(ui, response.id);
+
+            *low = config.snap_value(config.denormalize(low_raw, min, max), min, max);
+            *high = config.snap_value(config.denormalize(high_raw, min, max), min, max);
+            if (*low, *high) != before {
+                response.mark_changed();
+            }
+        }
+
+        let renderer =
+            KnobRenderer::new(&config, *high, high_raw, min, max).with_range_start(*low, low_raw);
+        let mut painter = ui.painter().clone();
+        if inert {
+            painter.multiply_opacity(ui.visuals().disabled_alpha());
+        }
+        renderer.render_knob(&painter, center, radius, response.hovered() && !inert);
+        let label_truncated = renderer.render_label(&painter, rect, knob_rect);
+
+        let show_tooltip = config.tooltip_only_labels
+            || label_truncated
+            || (config.label.is_some() && matches!(config.tooltip, TooltipMode::Value))
+            || matches!(config.tooltip, TooltipMode::AlwaysValue);
+        let value_text = range_text(&config.format_value(*low), &config.format_value(*high));
+        let label = config.label.as_deref().unwrap_or_default();
+        let enabled = ui.is_enabled() && !inert;
+        response.widget_info(|| WidgetInfo::slider(enabled, *high as f64, label));
+        ui.ctx().accesskit_node_builder(response.id, |builder| {
+            builder.set_min_numeric_value(min.min(max) as f64);
+            builder.set_max_numeric_value(min.max(max) as f64);
+            builder.set_value(value_text.clone());
+        });
+
+        if response.hovered() && show_tooltip && !matches!(config.tooltip, TooltipMode::Off) {
+            let text = match &config.label {
+                Some(label) => format!("{label}: {value_text}"),
+                None => value_text,
+            };
+            response.clone().on_hover_text(text);
+        }
+
+        response
+    }
+}
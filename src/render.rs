@@ -1,7 +1,16 @@
-use egui::{Align2, Color32, Painter, Pos2, Rect, Stroke, Ui, Vec2};
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+use egui::emath::{GuiRounding, Rot2};
+use egui::text::{LayoutJob, TextWrapping};
+use egui::{
+    Align, Align2, Color32, FontFamily, FontId, Galley, Id, Mesh, Painter, Pos2, Rect, Shape,
+    Stroke, TextFormat, Vec2,
+};
 
 use crate::config::KnobConfig;
-use crate::style::{KnobStyle, LabelPosition};
+use crate::math;
+use crate::style::{ArcPlacement, DeltaReadout, KnobStyle, LabelPosition};
 
 pub(crate) struct KnobRenderer<'a> {
     config: &'a KnobConfig,
@@ -9,6 +18,12 @@ pub(crate) struct KnobRenderer<'a> {
     raw: f32,
     min: f32,
     max: f32,
+    peak: Option<f32>,
+    drag_delta: Option<f32>,
+    ghost: Option<f32>,
+    pulse: f32,
+    range_start: Option<(f32, f32)>,
+    exceeded: Option<f32>,
 }
 
 impl<'a> KnobRenderer<'a> {
@@ -19,188 +34,966 @@ impl<'a> KnobRenderer<'a> {
             raw,
             min,
             max,
+            peak: None,
+            drag_delta: None,
+            ghost: None,
+            pulse: 0.0,
+            range_start: None,
+            exceeded: None,
         }
     }
 
+    /// Sets the end of the sweep, `0.0` or `1.0`, that the value lies beyond
+    pub fn with_exceeded(mut self, end: Option<f32>) -> Self {
+        self.exceeded = end;
+        self
+    }
+
+    /// Sets the normalized position of the peak-hold marker
+    pub fn with_peak(mut self, peak: Option<f32>) -> Self {
+        self.peak = peak;
+        self
+    }
+
+    /// Sets the change since the drag started, shown by [`DeltaReadout::Label`]
+    pub fn with_drag_delta(mut self, delta: Option<f32>) -> Self {
+        self.drag_delta = delta;
+        self
+    }
+
+    /// Sets the normalized position of the dimmed pre-drag marker
+    pub fn with_ghost(mut self, ghost: Option<f32>) -> Self {
+        self.ghost = ghost;
+        self
+    }
+
+    /// Sets the intensity of the external-change highlight in `0.0..=1.0`
+    pub fn with_pulse(mut self, pulse: f32) -> Self {
+        self.pulse = pulse;
+        self
+    }
+
+    /// Makes this a range knob from `value` at normalized `raw` up to the knob's own value
+    pub fn with_range_start(mut self, value: f32, raw: f32) -> Self {
+        self.range_start = Some((value, raw));
+        self
+    }
+
     pub fn compute_angle(&self) -> f32 {
-        if self.min == self.max || self.raw.is_nan() {
+        if self.min == self.max {
             self.config.min_angle
         } else {
-            self.config.min_angle
-                + self.raw * (self.config.max_angle - self.config.min_angle)
+            self.angle_at(self.raw.clamp(0.0, 1.0))
+        }
+    }
+
+    /// Angle of a normalized position along the sweep
+    fn angle_at(&self, t: f32) -> f32 {
+        math::angle_at(t, self.config.min_angle, self.config.max_angle)
+    }
+
+    /// Extra space needed around the knob body to fit an outside value ring
+    fn ring_margin(&self) -> f32 {
+        match self.config.arc_placement {
+            ArcPlacement::Inside => 0.0,
+            ArcPlacement::Outside => {
+                self.config.stroke_width * 3.0 + self.spiral_pitch(0.0, true) * self.turns()
+            }
+        }
+    }
+
+    fn arc_radius(&self, radius: f32) -> f32 {
+        match self.config.arc_placement {
+            ArcPlacement::Inside => radius * 0.85,
+            ArcPlacement::Outside => radius + self.config.stroke_width * 2.0,
+        }
+    }
+
+    /// Number of turns of the sweep, in either direction
+    fn turns(&self) -> f32 {
+        (self.config.max_angle - self.config.min_angle).abs() / TAU
+    }
+
+    /// Change of an arc's radius per turn, for sweeps of more than one turn
+    ///
+    /// Winding the arcs of a multi-turn knob into a spiral keeps its turns apart instead of
+    /// drawing them over each other. Arcs inside the knob wind inwards and lose at most 40%
+    /// of `radius`, outside rings wind outwards.
+    fn spiral_pitch(&self, radius: f32, outwards: bool) -> f32 {
+        let turns = self.turns();
+        if turns <= 1.0 {
+            return 0.0;
+        }
+        let pitch = self.config.stroke_width * 1.6;
+        if outwards {
+            pitch
+        } else {
+            -pitch.min(radius * 0.4 / turns)
         }
     }
 
+    /// Radius change per turn of the value arc
+    fn value_pitch(&self, radius: f32) -> f32 {
+        let outwards = self.config.arc_placement == ArcPlacement::Outside;
+        self.spiral_pitch(self.arc_radius(radius), outwards)
+    }
+
+    /// Radius of the value arc at a normalized position, following the spiral
+    fn arc_radius_at(&self, radius: f32, t: f32) -> f32 {
+        self.arc_radius(radius) + self.value_pitch(radius) * t * self.turns()
+    }
+
+    /// Snaps the center and radius to the physical pixel grid for hairline strokes,
+    /// so that small knobs drawn with ~1 px lines stay crisp instead of blurry.
+    fn snap_to_pixels(&self, painter: &Painter, center: Pos2, radius: f32) -> (Pos2, f32) {
+        let pixels_per_point = painter.pixels_per_point();
+        if self.config.stroke_width * pixels_per_point > 1.5 {
+            return (center, radius);
+        }
+
+        (
+            center.round_to_pixel_center(pixels_per_point),
+            radius.round_to_pixels(pixels_per_point),
+        )
+    }
+
     pub fn render_knob(&self, painter: &Painter, center: Pos2, radius: f32, hovered: bool) {
+        let (center, radius) = self.snap_to_pixels(painter, center, radius);
+
         let knob_color = if hovered {
-            self.config.colors.knob_color.linear_multiply(1.15)
+            self.config.colors.hover_color
         } else {
             self.config.colors.knob_color
         };
 
-        // TODO: make an option
         painter.circle_filled(
             center,
             radius - self.config.stroke_width / 2.0,
-            self.config.colors.knob_color.gamma_multiply(0.15),
+            self.config.colors.cap_fill_color,
         );
 
-        painter.circle_stroke(
-            center,
-            radius,
-            Stroke::new(self.config.stroke_width, knob_color),
-        );
+        if self.config.learn_armed {
+            painter.circle_stroke(
+                center,
+                radius,
+                Stroke::new(
+                    self.config.stroke_width * 1.5,
+                    self.config.colors.arc_fill_color,
+                ),
+            );
+        } else if self.config.show_outline {
+            painter.circle_stroke(
+                center,
+                radius,
+                Stroke::new(self.config.stroke_width, knob_color),
+            );
+        }
+
+        if self.config.tick_count > 0 {
+            self.render_ticks(painter, center, radius);
+        }
+
+        if matches!(self.config.style, KnobStyle::Skirted) {
+            let cap_radius = radius * 0.6;
+            painter.circle_filled(center, cap_radius, self.config.colors.cap_fill_color);
+            if self.config.show_outline {
+                painter.circle_stroke(
+                    center,
+                    cap_radius,
+                    Stroke::new(self.config.stroke_width, knob_color),
+                );
+            }
+        }
+
+        if let Some(level) = self.config.meter_level {
+            self.render_meter(painter, center, radius, level);
+        }
 
         if self.config.show_background_arc {
             self.render_background_arc(painter, center, radius);
         }
 
-        let angle = self.compute_angle();
-        match self.config.style {
-            KnobStyle::Wiper => {
-                let pointer = center + Vec2::angled(angle) * (radius * 0.65);
-                painter.line_segment(
-                    [center, pointer],
-                    Stroke::new(
-                        self.config.stroke_width * 1.2,
-                        self.config.colors.line_color,
-                    ),
-                );
+        if let Some(peak) = self.peak {
+            self.render_peak_marker(painter, center, radius, peak);
+        }
+
+        if self.config.show_default_marker
+            && let Some(default_value) = self.config.default_value
+        {
+            let default_raw = self.config.normalize(default_value, self.min, self.max);
+            self.render_default_marker(painter, center, radius, default_raw);
+        }
+
+        if let Some(automated) = self.config.automation {
+            let automated = self.config.normalize(automated, self.min, self.max);
+            self.render_automation_marker(painter, center, radius, automated);
+        }
+
+        if let Some(end) = self.exceeded {
+            self.render_exceeded_marker(painter, center, radius, end);
+        }
+
+        if let Some(ghost) = self.ghost {
+            let color = self.config.colors.line_color.gamma_multiply(0.35);
+            if matches!(self.config.style, KnobStyle::Spread) {
+                self.render_spread(painter, center, radius, ghost, color);
+            } else {
+                self.render_indicator(painter, center, radius, self.angle_at(ghost), color);
             }
-            KnobStyle::Dot => {
-                let dot_pos = center + Vec2::angled(angle) * (radius * 0.7);
-                painter.circle_filled(
-                    dot_pos,
-                    self.config.stroke_width * 1.8,
-                    self.config.colors.line_color,
-                );
+        }
+
+        let line_color = if self.pulse > 0.0 {
+            self.config
+                .colors
+                .line_color
+                .lerp_to_gamma(Color32::WHITE, self.pulse * 0.7)
+        } else {
+            self.config.colors.line_color
+        };
+
+        if let Some((_, start)) = self.range_start {
+            self.render_indicator(painter, center, radius, self.angle_at(start), line_color);
+        }
+
+        if matches!(self.config.style, KnobStyle::Spread) && self.range_start.is_none() {
+            let raw = if self.min == self.max { 0.0 } else { self.raw };
+            self.render_spread(painter, center, radius, raw, line_color);
+        } else {
+            let angle = self.compute_angle();
+            self.render_indicator(painter, center, radius, angle, line_color);
+        }
+    }
+
+    /// Normalized positions of the two wipers of [`KnobStyle::Spread`], mirrored about
+    /// the middle of the sweep
+    fn spread_positions(raw: f32) -> (f32, f32) {
+        let half = raw.clamp(0.0, 1.0) / 2.0;
+        (0.5 - half, 0.5 + half)
+    }
+
+    fn render_spread(
+        &self,
+        painter: &Painter,
+        center: Pos2,
+        radius: f32,
+        raw: f32,
+        color: Color32,
+    ) {
+        let (from, to) = Self::spread_positions(raw);
+        for t in [from, to] {
+            self.render_indicator(painter, center, radius, self.angle_at(t), color);
+        }
+    }
+
+    fn render_indicator(
+        &self,
+        painter: &Painter,
+        center: Pos2,
+        radius: f32,
+        angle: f32,
+        color: Color32,
+    ) {
+        let stroke = Stroke::new(self.config.stroke_width, color);
+        let direction = Vec2::angled(angle);
+        match &self.config.style {
+            KnobStyle::Wiper | KnobStyle::Spread => {
+                paint_wiper(painter, center, radius, direction, stroke);
             }
+            KnobStyle::Dot => paint_dot(painter, center, radius, direction, stroke),
+            KnobStyle::Skirted => paint_skirted(painter, center, radius, direction, stroke),
+            KnobStyle::Custom(indicator) => indicator.paint(painter, center, radius, angle, stroke),
         }
     }
 
+    fn render_ticks(&self, painter: &Painter, center: Pos2, radius: f32) {
+        let count = self.config.tick_count;
+        let stroke = Stroke::new(
+            (self.config.stroke_width * 0.5).max(1.0),
+            self.config.colors.tick_color,
+        );
+
+        for i in 0..count {
+            let t = if count > 1 {
+                i as f32 / (count - 1) as f32
+            } else {
+                0.5
+            };
+            let direction = Vec2::angled(self.angle_at(t));
+            painter.line_segment(
+                [
+                    center + direction * (radius * 0.88),
+                    center + direction * (radius * 0.98),
+                ],
+                stroke,
+            );
+        }
+    }
+
+    /// Draws only the background arc with its fill, e.g. as a second ring around a knob
+    pub fn render_ring(&self, painter: &Painter, center: Pos2, radius: f32) {
+        let (center, radius) = self.snap_to_pixels(painter, center, radius);
+        self.render_background_arc(painter, center, radius);
+    }
+
     fn render_background_arc(&self, painter: &Painter, center: Pos2, radius: f32) {
-        let arc_start = self.config.min_angle;
-        let arc_end = self.config.max_angle;
-        let segments = 128;
-        let arc_color = self.config.colors.knob_color.gamma_multiply(0.35);
-        let arc_radius = radius * 0.85;
-
-        let mut points = Vec::with_capacity(segments + 1);
-        for i in 0..=segments {
-            let t = i as f32 / segments as f32;
-            let angle = arc_start + (arc_end - arc_start) * t;
-            let pos = center + Vec2::angled(angle) * arc_radius;
-            points.push(pos);
-        }
-
-        painter.add(egui::Shape::line(
-            points,
-            Stroke::new(self.config.stroke_width, arc_color),
+        let arc_radius = self.arc_radius(radius);
+        let pitch = self.value_pitch(radius);
+
+        painter.add(self.arc_shape(
+            center,
+            arc_radius,
+            pitch,
+            0.0,
+            1.0,
+            Stroke::new(
+                self.config.stroke_width,
+                self.config.colors.arc_background_color,
+            ),
         ));
 
-        if self.config.show_filled_segments {
-            let filled_segments = (segments as f32
-                * self.raw.clamp(0.0, 1.0))
-                as usize;
-
-            if filled_segments > 0 {
-                let mut fill_points = Vec::with_capacity(filled_segments + 1);
-                for i in 0..=filled_segments {
-                    let t = i as f32 / segments as f32;
-                    let angle = arc_start + (arc_end - arc_start) * t;
-                    let pos = center + Vec2::angled(angle) * arc_radius;
-                    fill_points.push(pos);
-                }
+        if !self.config.show_filled_segments {
+            return;
+        }
 
-                painter.add(egui::Shape::line(
-                    fill_points,
-                    Stroke::new(
-                        self.config.stroke_width * 1.2,
-                        self.config.colors.line_color,
-                    ),
-                ));
+        let fill_stroke = Stroke::new(
+            self.config.stroke_width * 1.2,
+            self.config.colors.arc_fill_color,
+        );
+
+        if let Some((_, start)) = self.range_start {
+            let (from, to) = (start.clamp(0.0, 1.0), self.raw.clamp(0.0, 1.0));
+            if from != to {
+                painter.add(self.arc_shape(center, arc_radius, pitch, from, to, fill_stroke));
+            }
+        } else if matches!(self.config.style, KnobStyle::Spread) {
+            let (from, to) = Self::spread_positions(self.raw);
+            if from != to {
+                painter.add(self.arc_shape(center, arc_radius, pitch, from, to, fill_stroke));
+            }
+        } else if self.config.bipolar_fill {
+            let value = self.raw.clamp(0.0, 1.0);
+            if value != 0.5 {
+                painter.add(self.arc_shape(center, arc_radius, pitch, 0.5, value, fill_stroke));
             }
+        } else {
+            let value = self.raw.clamp(0.0, 1.0);
+            if value > 0.0 {
+                painter.add(self.arc_shape(center, arc_radius, pitch, 0.0, value, fill_stroke));
+            }
+        }
+    }
+
+    /// Directions along the sweep between two normalized positions, both ends included
+    ///
+    /// Each direction is the previous one rotated by a fixed step, which saves evaluating
+    /// `sin` and `cos` for every point of every arc. The iterator knows its exact length,
+    /// so collecting the points allocates once, with the final capacity. The last direction
+    /// is computed exactly, so the arc ends right at `to` however many steps it took.
+    /// Each direction comes with its normalized position.
+    fn arc_directions(
+        &self,
+        from: f32,
+        to: f32,
+    ) -> impl ExactSizeIterator<Item = (f32, Vec2)> + use<> {
+        let share = self.config.arc_segments as f32 * (to - from).abs();
+        let segments = (share.ceil() as usize).max(1);
+        let (start, end) = (self.angle_at(from), self.angle_at(to));
+        let step = Rot2::from_angle((end - start) / segments as f32);
+        let mut direction = Vec2::angled(start);
+        (0..segments + 1).map(move |i| {
+            let current = if i == segments {
+                Vec2::angled(end)
+            } else {
+                direction
+            };
+            direction = step * direction;
+            (from + (to - from) * i as f32 / segments as f32, current)
+        })
+    }
+
+    /// Arc between two normalized positions, as a polyline or as a filled ring sector
+    ///
+    /// The radius changes by `pitch` per turn of the sweep, see [`Self::spiral_pitch`].
+    ///
+    /// The ring sector is a triangle mesh, which avoids the self-overlap of fat polylines
+    /// at high stroke widths and tight radii.
+    ///
+    /// The shape owns its points until egui tessellates it at the end of the frame, so they
+    /// can't live in a buffer reused across frames. Instead each arc makes a single
+    /// allocation of exactly the needed size.
+    fn arc_shape(
+        &self,
+        center: Pos2,
+        radius: f32,
+        pitch: f32,
+        from: f32,
+        to: f32,
+        stroke: Stroke,
+    ) -> Shape {
+        let turns = self.turns();
+        let radius_at = |t: f32| radius + pitch * t * turns;
+        let directions = self.arc_directions(from, to);
+        if !self.config.mesh_arcs {
+            let points = directions
+                .map(|(t, direction)| center + direction * radius_at(t))
+                .collect();
+            return Shape::line(points, stroke);
+        }
+
+        let mut mesh = Mesh::default();
+        mesh.reserve_vertices(directions.len() * 2);
+        mesh.reserve_triangles((directions.len() - 1) * 2);
+        for (i, (t, direction)) in directions.enumerate() {
+            let radius = radius_at(t);
+            let inner = radius - stroke.width / 2.0;
+            let outer = radius + stroke.width / 2.0;
+            mesh.colored_vertex(center + direction * inner, stroke.color);
+            mesh.colored_vertex(center + direction * outer, stroke.color);
+            if i > 0 {
+                let index = 2 * i as u32;
+                mesh.add_triangle(index - 2, index - 1, index);
+                mesh.add_triangle(index - 1, index + 1, index);
+            }
+        }
+        Shape::mesh(mesh)
+    }
+
+    fn render_meter(&self, painter: &Painter, center: Pos2, radius: f32, level: f32) {
+        let level = if level.is_nan() {
+            0.0
+        } else {
+            level.clamp(0.0, 1.0)
+        };
+        let meter_radius = radius * 0.45;
+        let pitch = self.spiral_pitch(meter_radius, false);
+        let stroke_width = (self.config.stroke_width * 0.75).max(1.0);
+
+        painter.add(self.arc_shape(
+            center,
+            meter_radius,
+            pitch,
+            0.0,
+            1.0,
+            Stroke::new(
+                stroke_width,
+                self.config.colors.meter_color.gamma_multiply(0.25),
+            ),
+        ));
+
+        if level > 0.0 {
+            painter.add(self.arc_shape(
+                center,
+                meter_radius,
+                pitch,
+                0.0,
+                level,
+                Stroke::new(stroke_width, self.config.colors.meter_color),
+            ));
         }
     }
 
-    pub fn render_label(&self, ui: &Ui, rect: Rect) {
+    fn render_peak_marker(&self, painter: &Painter, center: Pos2, radius: f32, peak: f32) {
+        let peak = peak.clamp(0.0, 1.0);
+        let direction = Vec2::angled(self.angle_at(peak));
+        let arc_radius = self.arc_radius_at(radius, peak);
+        let half_length = self.config.stroke_width * 1.5;
+
+        painter.line_segment(
+            [
+                center + direction * (arc_radius - half_length),
+                center + direction * (arc_radius + half_length),
+            ],
+            Stroke::new(self.config.stroke_width, self.config.colors.tick_color),
+        );
+    }
+
+    /// Short tick just inside the arc at the default value
+    fn render_default_marker(&self, painter: &Painter, center: Pos2, radius: f32, raw: f32) {
+        if raw.is_nan() {
+            return;
+        }
+        let raw = raw.clamp(0.0, 1.0);
+        let direction = Vec2::angled(self.angle_at(raw));
+        let arc_radius = self.arc_radius_at(radius, raw);
+
+        painter.line_segment(
+            [
+                center + direction * (arc_radius - self.config.stroke_width * 2.5),
+                center + direction * (arc_radius - self.config.stroke_width),
+            ],
+            Stroke::new(
+                self.config.stroke_width * 0.5,
+                self.config.colors.tick_color,
+            ),
+        );
+    }
+
+    /// Dot on the arc at the host's automated value
+    fn render_automation_marker(&self, painter: &Painter, center: Pos2, radius: f32, raw: f32) {
+        if raw.is_nan() {
+            return;
+        }
+        let raw = raw.clamp(0.0, 1.0);
+        let direction = Vec2::angled(self.angle_at(raw));
+        painter.circle_filled(
+            center + direction * self.arc_radius_at(radius, raw),
+            self.config.stroke_width * 1.2,
+            self.config.colors.tick_color,
+        );
+    }
+
+    /// Warning-colored tick beyond the arc at the end of the sweep the value lies past
+    ///
+    /// Where that would leave the space allocated around the knob, e.g. for an outside
+    /// ring, the tick moves inwards across the arc.
+    fn render_exceeded_marker(&self, painter: &Painter, center: Pos2, radius: f32, end: f32) {
+        let direction = Vec2::angled(self.angle_at(end));
+        let arc_radius = self.arc_radius_at(radius, end);
+        let stroke_width = self.config.stroke_width;
+        let outer =
+            (arc_radius + stroke_width * 3.0).min(radius + self.ring_margin() + stroke_width * 0.5);
+        let color = painter.ctx().global_style().visuals.warn_fg_color;
+
+        painter.line_segment(
+            [
+                center + direction * (outer - stroke_width * 2.0),
+                center + direction * outer,
+            ],
+            Stroke::new(stroke_width, color),
+        );
+    }
+
+    /// Text shown next to the value, honoring the drag delta readout
+    fn value_text(&self) -> String {
+        if let Some((start, _)) = self.range_start {
+            return range_text(
+                &self.config.format_value(start),
+                &self.config.format_value(self.value),
+            );
+        }
+        match self.drag_delta {
+            Some(delta) if self.config.delta_readout == Some(DeltaReadout::Label) => {
+                self.config.format_delta(delta)
+            }
+            _ => self.config.format_value(self.value),
+        }
+    }
+
+    /// Right-aligns the value to the width of the longest formatted range end
+    fn pad_value(&self, text: String) -> String {
+        let width = [self.min, self.max]
+            .map(|v| self.config.format_value(v).chars().count())
+            .into_iter()
+            .max()
+            .unwrap_or(0);
+        format!("{text:>width$}")
+    }
+
+    /// Builds the layout jobs for the name and value labels
+    ///
+    /// Without a separate value position, name and value share one `"Name: value"` label.
+    /// The value is left out entirely when hidden, and nothing is laid out in tooltip-only mode.
+    fn label_jobs(&self, value_text: String) -> Vec<LabelJob> {
+        let mut name_font = self.config.name_font();
+        let mut value_font = self.config.value_font();
+        let mut jobs = Vec::new();
+        if self.config.tooltip_only_labels {
+            return jobs;
+        }
+
+        let value_text = if self.config.fixed_width_value {
+            value_font.family = FontFamily::Monospace;
+            if self.config.value_position.is_none() {
+                name_font.family = FontFamily::Monospace;
+            }
+            self.pad_value(value_text)
+        } else {
+            value_text
+        };
+
+        let value_position = self
+            .config
+            .value_position
+            .filter(|_| self.config.show_value);
         if let Some(label) = &self.config.label {
-            let label_text = format!("{}: {}", label, (self.config.label_format)(self.value));
-            let font_id = egui::FontId::proportional(self.config.font_size);
-            let label_padding = 4.0;
+            let with_value = self.config.show_value && value_position.is_none();
+            let text = if with_value {
+                format!("{}: {}", label, value_text)
+            } else {
+                label.clone()
+            };
+            let wrapping = match (self.config.label_wrap_width, self.config.label_max_width) {
+                (Some(wrap_width), _) => TextWrapping::wrap_at_width(wrap_width),
+                (None, Some(max_width)) => TextWrapping::truncate_at_width(max_width),
+                (None, None) => TextWrapping::no_max_width(),
+            };
+            jobs.push(LabelJob::new(
+                text,
+                name_font,
+                self.config.colors.text_color,
+                self.config.label_position,
+                wrapping,
+            ));
+        }
+
+        if let Some(value_position) = value_position {
+            jobs.push(LabelJob::new(
+                value_text,
+                value_font,
+                self.config.colors.value_text_color,
+                value_position,
+                TextWrapping::no_max_width(),
+            ));
+        }
+
+        jobs
+    }
+
+    /// Lays out the name and value labels
+    fn label_texts(&self, painter: &Painter, value_text: String) -> Vec<LabelText> {
+        self.label_jobs(value_text)
+            .into_iter()
+            .map(|job| job.layout(painter))
+            .collect()
+    }
+
+    /// Draws the labels, returns `true` if any of them had to be truncated
+    pub fn render_label(&self, painter: &Painter, rect: Rect, knob_rect: Rect) -> bool {
+        let texts = self.label_texts(painter, self.value_text());
+        let label_padding = 4.0;
+        let center = knob_rect.center();
+        let (column_x, column_align) = match self.config.knob_align {
+            Align::Min => (knob_rect.min.x, Align2::LEFT_TOP),
+            Align::Center => (center.x, Align2::CENTER_TOP),
+            Align::Max => (knob_rect.max.x, Align2::RIGHT_TOP),
+        };
 
-            let (label_pos, alignment) = match self.config.label_position {
+        for position in LabelPosition::ALL {
+            let block = LabelText::block_size(&texts, position);
+            if block == Vec2::ZERO {
+                continue;
+            }
+
+            let (anchor, alignment) = match position {
                 LabelPosition::Top => (
-                    Vec2::new(rect.center().x, rect.min.y + label_padding),
-                    Align2::CENTER_TOP,
+                    Pos2::new(column_x, rect.min.y + label_padding),
+                    column_align,
                 ),
                 LabelPosition::Bottom => (
-                    Vec2::new(rect.center().x, rect.max.y - label_padding),
-                    Align2::CENTER_BOTTOM,
+                    Pos2::new(column_x, rect.max.y - label_padding - block.y),
+                    column_align,
                 ),
                 LabelPosition::Left => (
-                    Vec2::new(rect.min.x + label_padding, rect.center().y),
-                    Align2::LEFT_CENTER,
+                    Pos2::new(rect.min.x + label_padding, center.y - block.y / 2.0),
+                    Align2::LEFT_TOP,
                 ),
                 LabelPosition::Right => (
-                    Vec2::new(rect.max.x - label_padding, rect.center().y),
-                    Align2::RIGHT_CENTER,
+                    Pos2::new(rect.max.x - label_padding, center.y - block.y / 2.0),
+                    Align2::RIGHT_TOP,
+                ),
+                LabelPosition::Center | LabelPosition::Custom(_) => (
+                    Pos2::new(center.x, center.y - block.y / 2.0),
+                    Align2::CENTER_TOP,
                 ),
             };
 
-            ui.painter().text(
-                label_pos.to_pos2(),
-                alignment,
-                label_text,
-                font_id,
-                self.config.colors.text_color,
-            );
+            let mut y = anchor.y;
+            for text in texts.iter().filter(|text| text.position == position) {
+                let text_rect = alignment.anchor_size(Pos2::new(anchor.x, y), text.galley.size());
+                text.paint(painter, text_rect);
+                y += text.galley.size().y;
+            }
+        }
+
+        for text in &texts {
+            if let LabelPosition::Custom(offset) = text.position {
+                let text_rect =
+                    Align2::CENTER_CENTER.anchor_size(center + offset, text.galley.size());
+                text.paint(painter, text_rect);
+            }
         }
+
+        texts.iter().any(|text| text.galley.elided)
     }
 
-    pub fn calculate_size(&self, ui: &Ui) -> Vec2 {
-        let knob_size = Vec2::splat(self.config.size + self.config.stroke_width * 2.0);
+    /// Formatted values the labels have to make room for
+    ///
+    /// Either end of the range can be the widest, and a template of the longer one with
+    /// every digit set to `0` covers intermediate values in fonts without tabular digits.
+    /// Signed deltas and the caller's own widest text are included when they may be shown.
+    fn value_candidates(&self) -> Vec<String> {
+        let ends = [self.min, self.max].map(|v| self.config.format_value(v));
+        let longest = ends.iter().max_by_key(|text| text.chars().count());
+        let template = longest.map(|text| {
+            text.chars()
+                .map(|c| if c.is_ascii_digit() { '0' } else { c })
+                .collect::<String>()
+        });
+        let mut candidates: Vec<String> = ends.into_iter().chain(template).collect();
+        if self.config.delta_readout == Some(DeltaReadout::Label) {
+            let span = self.max - self.min;
+            candidates.extend([span, -span].map(|delta| self.config.format_delta(delta)));
+        }
+        candidates.extend(self.config.widest_value.clone());
+        if self.range_start.is_some() {
+            // The widest range text is the widest single value twice
+            candidates = candidates
+                .iter()
+                .map(|text| range_text(text, text))
+                .collect();
+        }
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
 
-        let label_size = if let Some(label) = &self.config.label {
-            let font_id = egui::FontId::proportional(self.config.font_size);
-            let max_text = format!("{}: {}", label, (self.config.label_format)(self.max));
-            ui.painter()
-                .layout(max_text, font_id, Color32::WHITE, f32::INFINITY)
-                .size()
-        } else {
-            Vec2::ZERO
+    /// Identifies [`Self::value_candidates`] by the settings they are formatted from
+    ///
+    /// `None` with a custom format function, whose output can only be known by calling it.
+    fn candidates_key(&self) -> Option<Id> {
+        let config = self.config;
+        if config.label_format.is_some() {
+            return None;
+        }
+        Some(Id::new("egui_knob_candidates").with((
+            (self.min.to_bits(), self.max.to_bits()),
+            (config.auto_decimals, config.si_digits, &config.unit),
+            (
+                config.locale.decimal_separator,
+                config.locale.group_separator,
+            ),
+            config.delta_readout == Some(DeltaReadout::Label),
+            &config.widest_value,
+            self.range_start.is_some(),
+        )))
+    }
+
+    /// Identifies a measured layout by everything [`Self::label_jobs`] sizes text with
+    ///
+    /// Hashing the inputs instead of the built jobs keeps cache hits free of text layout
+    /// work. Colors are left out, they don't affect the size.
+    fn layout_id(&self, candidates_key: Id, knob_size: f32, pixels_per_point: f32) -> Id {
+        let config = self.config;
+        let position_index =
+            |position: LabelPosition| LabelPosition::ALL.iter().position(|p| *p == position);
+        Id::new("egui_knob_layout").with((
+            candidates_key,
+            &config.label,
+            position_index(config.label_position),
+            config.value_position.map(position_index),
+            (
+                config.show_value,
+                config.fixed_width_value,
+                config.tooltip_only_labels,
+            ),
+            (config.name_font(), config.value_font()),
+            config.label_wrap_width.map(f32::to_bits),
+            config.label_max_width.map(f32::to_bits),
+            knob_size.to_bits(),
+            config.label_offset.to_bits(),
+            pixels_per_point.to_bits(),
+        ))
+    }
+
+    /// Measures the widget, reserving label space for the widest expected value
+    ///
+    /// The measurement only depends on the label texts, fonts and a few sizes, so it is
+    /// kept in egui memory and reused as long as none of those change. Values in the
+    /// built-in format are only formatted when measuring, while a custom format function
+    /// still runs every frame to tell whether its texts changed.
+    pub fn calculate_layout(&self, painter: &Painter) -> KnobLayout {
+        let knob_size =
+            self.config.size + self.ring_margin() * 2.0 + self.config.stroke_width * 2.0;
+        let has_labels = !self.config.tooltip_only_labels
+            && (self.config.label.is_some()
+                || (self.config.show_value && self.config.value_position.is_some()));
+        if !has_labels {
+            return KnobLayout {
+                knob_size,
+                ..Default::default()
+            };
+        }
+
+        let (candidates, candidates_key) = match self.candidates_key() {
+            Some(key) => (None, key),
+            None => {
+                let candidates = self.value_candidates();
+                let key = Id::new(&candidates);
+                (Some(candidates), key)
+            }
         };
+        let cache_id = self.layout_id(candidates_key, knob_size, painter.pixels_per_point());
+        if let Some(layout) = painter.ctx().data(|d| d.get_temp::<KnobLayout>(cache_id)) {
+            return layout;
+        }
 
         let label_padding = 8.0;
+        let along = |size: f32| size + label_padding + self.config.label_offset;
+        let mut layout = KnobLayout {
+            knob_size,
+            ..Default::default()
+        };
 
-        match self.config.label_position {
-            LabelPosition::Top | LabelPosition::Bottom => Vec2::new(
-                knob_size.x.max(label_size.x + label_padding * 2.0),
-                knob_size.y + label_size.y + label_padding + self.config.label_offset,
-            ),
-            LabelPosition::Left | LabelPosition::Right => Vec2::new(
-                knob_size.x + label_size.x + label_padding + self.config.label_offset,
-                knob_size.y.max(label_size.y + label_padding),
-            ),
+        for text in candidates.unwrap_or_else(|| self.value_candidates()) {
+            let texts = self.label_texts(painter, text);
+            for position in LabelPosition::ALL {
+                let block = LabelText::block_size(&texts, position);
+                if block == Vec2::ZERO {
+                    continue;
+                }
+
+                match position {
+                    LabelPosition::Top => {
+                        layout.top = layout.top.max(along(block.y));
+                        layout.min_width = layout.min_width.max(block.x + label_padding * 2.0);
+                    }
+                    LabelPosition::Bottom => {
+                        layout.bottom = layout.bottom.max(along(block.y));
+                        layout.min_width = layout.min_width.max(block.x + label_padding * 2.0);
+                    }
+                    LabelPosition::Left => {
+                        layout.left = layout.left.max(along(block.x));
+                        layout.min_height = layout.min_height.max(block.y + label_padding);
+                    }
+                    LabelPosition::Right => {
+                        layout.right = layout.right.max(along(block.x));
+                        layout.min_height = layout.min_height.max(block.y + label_padding);
+                    }
+                    LabelPosition::Center | LabelPosition::Custom(_) => {}
+                }
+            }
         }
+
+        painter.ctx().data_mut(|d| d.insert_temp(cache_id, layout));
+        layout
     }
+}
 
-    pub fn calculate_knob_rect(&self, rect: Rect) -> Rect {
-        let knob_size = Vec2::splat(self.config.size);
+/// A line from the center towards the edge
+fn paint_wiper(painter: &Painter, center: Pos2, radius: f32, direction: Vec2, stroke: Stroke) {
+    let pointer = center + direction * (radius * 0.65);
+    painter.line_segment(
+        [center, pointer],
+        Stroke::new(stroke.width * 1.2, stroke.color),
+    );
+}
 
-        match self.config.label_position {
-            LabelPosition::Left => {
-                Rect::from_min_size(rect.right_top() + Vec2::new(-knob_size.x, 0.0), knob_size)
-            }
-            LabelPosition::Right => Rect::from_min_size(rect.left_top(), knob_size),
-            LabelPosition::Top => Rect::from_min_size(
-                rect.left_bottom() + Vec2::new((rect.width() - knob_size.x) / 2.0, -knob_size.y),
-                knob_size,
-            ),
-            LabelPosition::Bottom => Rect::from_min_size(
-                rect.left_top() + Vec2::new((rect.width() - knob_size.x) / 2.0, 0.0),
-                knob_size,
-            ),
+/// A dot near the edge
+fn paint_dot(painter: &Painter, center: Pos2, radius: f32, direction: Vec2, stroke: Stroke) {
+    let dot_pos = center + direction * (radius * 0.7);
+    painter.circle_filled(dot_pos, stroke.width * 1.8, stroke.color);
+}
+
+/// A line across the skirt, outside the cap
+fn paint_skirted(painter: &Painter, center: Pos2, radius: f32, direction: Vec2, stroke: Stroke) {
+    painter.line_segment(
+        [
+            center + direction * (radius * 0.6),
+            center + direction * radius,
+        ],
+        Stroke::new(stroke.width * 1.2, stroke.color),
+    );
+}
+
+/// Both ends of a range as one text, e.g. `"20 Hz – 2 kHz"`
+pub(crate) fn range_text(low: &str, high: &str) -> String {
+    format!("{low} – {high}")
+}
+
+/// Label text that is ready to be laid out
+struct LabelJob {
+    job: LayoutJob,
+    color: Color32,
+    position: LabelPosition,
+}
+
+impl LabelJob {
+    fn new(
+        text: String,
+        font_id: FontId,
+        color: Color32,
+        position: LabelPosition,
+        wrapping: TextWrapping,
+    ) -> Self {
+        let mut job = LayoutJob::single_section(text, TextFormat::simple(font_id, color));
+        job.wrap = wrapping;
+        job.halign = match position {
+            LabelPosition::Left => Align::Min,
+            LabelPosition::Right => Align::Max,
+            _ => Align::Center,
+        };
+        Self {
+            job,
+            color,
+            position,
         }
     }
+
+    /// Lays out the text, relying on egui's own galley cache between frames
+    fn layout(self, painter: &Painter) -> LabelText {
+        LabelText {
+            galley: painter.layout_job(self.job),
+            color: self.color,
+            position: self.position,
+        }
+    }
+}
+
+/// A laid out piece of label text and where it goes
+struct LabelText {
+    galley: Arc<Galley>,
+    color: Color32,
+    position: LabelPosition,
+}
+
+impl LabelText {
+    /// Paints the text so that its bounds cover `rect`
+    fn paint(&self, painter: &Painter, rect: Rect) {
+        let origin = rect.min - self.galley.rect.min.to_vec2();
+        painter.galley(origin, self.galley.clone(), self.color);
+    }
+
+    /// Size of all texts at `position`, stacked top to bottom
+    fn block_size(texts: &[Self], position: LabelPosition) -> Vec2 {
+        texts
+            .iter()
+            .filter(|text| text.position == position)
+            .fold(Vec2::ZERO, |block, text| {
+                let size = text.galley.size();
+                Vec2::new(block.x.max(size.x), block.y + size.y)
+            })
+    }
+}
+
+/// Space taken by the knob and by the labels on each of its sides
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct KnobLayout {
+    knob_size: f32,
+    top: f32,
+    bottom: f32,
+    left: f32,
+    right: f32,
+    min_width: f32,
+    min_height: f32,
+}
+
+impl KnobLayout {
+    /// Total size to allocate for the widget
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(
+            self.left + self.knob_size.max(self.min_width) + self.right,
+            self.top + self.knob_size.max(self.min_height) + self.bottom,
+        )
+    }
+
+    /// Square occupied by the knob within the allocated `rect`
+    ///
+    /// `align` places the knob horizontally when labels make the space wider than the knob.
+    pub fn knob_rect(&self, rect: Rect, align: Align) -> Rect {
+        let inner = Rect::from_min_max(
+            rect.min + Vec2::new(self.left, self.top),
+            rect.max - Vec2::new(self.right, self.bottom),
+        );
+        Align2([align, Align::Center]).align_size_within_rect(Vec2::splat(self.knob_size), inner)
+    }
 }
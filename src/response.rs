@@ -0,0 +1,84 @@
+use std::ops::{Deref, DerefMut};
+
+use egui::Response;
+
+/// What happened to a [`Knob`](crate::Knob) this frame
+///
+/// Returned by [`Knob::show`](crate::Knob::show). Derefs to the underlying [`Response`],
+/// so e.g. `drag_started()` and `drag_stopped()` are available directly.
+///
+/// `changed()` is set in every frame the user changed the value, whether by dragging,
+/// scrolling, keys, a reset or typing, and in every step of an animation started with
+/// [`Knob::animate_to`](crate::Knob::animate_to). Other changes made by the application
+/// only show up as [`ChangeCause::External`].
+#[derive(Debug, Clone)]
+pub struct KnobResponse {
+    /// Response of the knob widget
+    pub response: Response,
+    /// Value before this frame's interaction
+    pub old_value: f32,
+    /// Value after this frame's interaction
+    pub value: f32,
+    /// Value after this frame's interaction, mapped to `0.0..=1.0` along the sweep
+    pub normalized: f32,
+    /// Why the value changed this frame, `None` if it didn't
+    pub cause: Option<ChangeCause>,
+    /// Whether a bound value outside the range was clamped into it this frame
+    ///
+    /// See [`OutOfRange`](crate::OutOfRange) for when that happens.
+    pub clamped: bool,
+    /// Whether the knob ignored input because its range is empty or not finite
+    ///
+    /// Such a knob is drawn dimmed with the indicator parked at the start of the sweep.
+    pub inert: bool,
+}
+
+/// What changed the value of a knob
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeCause {
+    /// Dragged with the pointer
+    Drag,
+    /// Turned with the scroll wheel
+    Scroll,
+    /// Stepped with the arrow keys or by assistive technology
+    Keyboard,
+    /// Reset to its default
+    ///
+    /// By a double-click, a Cmd/Ctrl-click, the click action of assistive technology, or
+    /// by springing back when released with
+    /// [`Knob::with_spring_return`](crate::Knob::with_spring_return).
+    Reset,
+    /// Set to a typed or pasted value
+    TextEntry,
+    /// Moved by an animation started with [`Knob::animate_to`](crate::Knob::animate_to)
+    Animation,
+    /// Changed by the application since the previous frame
+    External,
+}
+
+impl KnobResponse {
+    /// How much the value moved this frame
+    pub fn value_delta(&self) -> f32 {
+        self.value - self.old_value
+    }
+}
+
+impl Deref for KnobResponse {
+    type Target = Response;
+
+    fn deref(&self) -> &Response {
+        &self.response
+    }
+}
+
+impl DerefMut for KnobResponse {
+    fn deref_mut(&mut self) -> &mut Response {
+        &mut self.response
+    }
+}
+
+impl From<KnobResponse> for Response {
+    fn from(response: KnobResponse) -> Self {
+        response.response
+    }
+}
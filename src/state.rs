@@ -0,0 +1,460 @@
+use egui::emath::easing;
+use egui::{Context, Id, Pos2, Response, Ui, lerp};
+
+/// Where a knob was drawn this frame, for custom overlays aligned with it
+///
+/// Kept in egui's temporary memory under the knob's id, read it back with
+/// [`KnobGeometry::from_response`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KnobGeometry {
+    /// Center of the knob
+    pub center: Pos2,
+    /// Radius of the painted knob body
+    pub radius: f32,
+    /// Angle of the indicator in radians, as used by [`egui::Vec2::angled`]
+    pub angle: f32,
+}
+
+impl KnobGeometry {
+    /// Geometry of the knob that produced `response`
+    pub fn from_response(response: &Response) -> Option<Self> {
+        response.ctx.data(|d| d.get_temp(response.id))
+    }
+
+    pub(crate) fn store(self, ui: &Ui, id: Id) {
+        ui.data_mut(|d| d.insert_temp(id, self));
+    }
+}
+
+/// Peak-hold bookkeeping for a single knob, kept in egui's temporary memory
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PeakState {
+    peak: f32,
+    time: f64,
+}
+
+impl PeakState {
+    /// Feeds the current normalized value and returns the normalized peak to display
+    ///
+    /// The peak is held for `hold_time` seconds, then falls by `decay_rate` per second
+    /// until it meets the current value again. Repaints are only requested while it falls.
+    pub fn update(ui: &Ui, id: Id, raw: f32, hold_time: f32, decay_rate: f32) -> f32 {
+        let now = ui.input(|i| i.time);
+        let state = ui.data(|d| d.get_temp::<Self>(id));
+        let held = state.map_or(raw, |state| state.current(now, hold_time, decay_rate));
+
+        match state {
+            Some(state) if raw < held => {
+                if decay_rate > 0.0 {
+                    let holding_for = hold_time - (now - state.time) as f32;
+                    if holding_for > 0.0 {
+                        ui.ctx().request_repaint_after_secs(holding_for);
+                    } else {
+                        ui.ctx().request_repaint();
+                    }
+                }
+                held
+            }
+            _ => {
+                let state = Self {
+                    peak: raw,
+                    time: now,
+                };
+                ui.data_mut(|d| d.insert_temp(id, state));
+                raw
+            }
+        }
+    }
+
+    fn current(&self, now: f64, hold_time: f32, decay_rate: f32) -> f32 {
+        let decaying_for = ((now - self.time) as f32 - hold_time).max(0.0);
+        self.peak - decay_rate * decaying_for
+    }
+}
+
+/// Smoothed needle position of a gauge-mode knob
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GaugeState {
+    displayed: f32,
+    /// `displayed` mapped back to the knob's range
+    displayed_value: f32,
+}
+
+impl GaugeState {
+    /// Moves the displayed normalized value towards `target` and returns it
+    ///
+    /// The needle follows with an exponential decay whose time constant is `response_time`
+    /// seconds. Repaints are requested until the needle settles.
+    pub fn smooth(
+        ui: &Ui,
+        id: Id,
+        target: f32,
+        response_time: f32,
+        denormalize: impl FnOnce(f32) -> f32,
+    ) -> f32 {
+        let dt = ui.input(|i| i.stable_dt);
+        let previous = ui
+            .data(|d| d.get_temp::<Self>(id))
+            .map_or(target, |state| state.displayed);
+
+        let mut displayed = if response_time > 0.0 && previous.is_finite() {
+            target + (previous - target) * (-dt / response_time).exp()
+        } else {
+            target
+        };
+
+        if (displayed - target).abs() < 1e-4 {
+            displayed = target;
+        } else {
+            ui.ctx().request_repaint();
+        }
+
+        let displayed_value = denormalize(displayed);
+        ui.data_mut(|d| {
+            d.insert_temp(
+                id,
+                Self {
+                    displayed,
+                    displayed_value,
+                },
+            );
+        });
+        displayed
+    }
+}
+
+/// A glide of the value towards a target, started with [`Knob::animate_to`](crate::Knob::animate_to)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ValueAnimation {
+    from_raw: f32,
+    to_raw: f32,
+    target: f32,
+    start_time: f64,
+    duration: f32,
+    /// Value before the first animation of an uninterrupted chain of them
+    start_value: f32,
+}
+
+/// The value an animation moved the knob to this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AnimationStep {
+    pub value: f32,
+    pub start_value: f32,
+    /// Whether this step landed on the target, ending the animation
+    pub finished: bool,
+}
+
+impl ValueAnimation {
+    /// Starts gliding from `value` at normalized `from_raw` to `target` at normalized `to_raw`
+    ///
+    /// Returns `false` if this replaces an animation that was still running, which then
+    /// continues as the same edit.
+    pub fn start(
+        ui: &Ui,
+        id: Id,
+        (value, from_raw): (f32, f32),
+        (target, to_raw): (f32, f32),
+        duration: f32,
+    ) -> bool {
+        let running = ui.data(|d| d.get_temp::<Self>(id));
+        let animation = Self {
+            from_raw,
+            to_raw,
+            target,
+            start_time: ui.input(|i| i.time),
+            duration,
+            start_value: running.map_or(value, |running| running.start_value),
+        };
+        ui.data_mut(|d| d.insert_temp(id, animation));
+        running.is_none()
+    }
+
+    /// Advances the animation of the knob with the given id
+    ///
+    /// Returns `None` when no animation runs. The last step lands exactly on the target.
+    pub fn advance(ui: &Ui, id: Id, denormalize: impl Fn(f32) -> f32) -> Option<AnimationStep> {
+        let animation = ui.data(|d| d.get_temp::<Self>(id))?;
+        let elapsed = (ui.input(|i| i.time) - animation.start_time) as f32;
+
+        let finished = elapsed >= animation.duration || !animation.duration.is_finite();
+        let value = if finished {
+            Self::cancel(ui, id);
+            animation.target
+        } else {
+            ui.ctx().request_repaint();
+            let t = easing::cubic_in_out(elapsed / animation.duration);
+            denormalize(lerp(animation.from_raw..=animation.to_raw, t))
+        };
+        Some(AnimationStep {
+            value,
+            start_value: animation.start_value,
+            finished,
+        })
+    }
+
+    pub fn cancel(ui: &Ui, id: Id) {
+        ui.data_mut(|d| d.remove::<Self>(id));
+    }
+}
+
+/// Value and normalized position captured when the current drag started
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DragState {
+    pub start_value: f32,
+    pub start_raw: f32,
+}
+
+/// Scroll steps that count as a single edit until scrolling pauses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ScrollBurst {
+    pub start_value: f32,
+    pub last_time: f64,
+}
+
+/// Interaction state of a knob that outlives a single frame
+///
+/// Kept in egui's temporary memory under the knob's id while the knob is being
+/// interacted with, and removed once it's idle again.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KnobState {
+    pub(crate) drag: Option<DragState>,
+    /// Drag movement smaller than one step, carried over to the next frame
+    pub(crate) step_remainder: f32,
+    /// Text typed into the value editor while it's open
+    pub(crate) edit_text: Option<String>,
+    /// Scrolling in progress, only tracked for the undo hook
+    pub(crate) scroll_burst: Option<ScrollBurst>,
+    /// Set from press to release of the pointer on the knob
+    pub(crate) touched: bool,
+}
+
+impl KnobState {
+    /// State of the knob with the given id, `None` while the knob is idle
+    ///
+    /// The id is the one of the knob's response, so the state can be queried right after
+    /// adding the knob, e.g. to coordinate surrounding widgets with a drag.
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data(|d| d.get_temp(id))
+    }
+
+    pub(crate) fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| {
+            if self == Self::default() {
+                d.remove::<Self>(id);
+            } else {
+                d.insert_temp(id, self);
+            }
+        });
+    }
+
+    /// Value the indicator of a gauge shows while it smoothly follows the bound value
+    ///
+    /// `None` unless the knob with the given id is in
+    /// [gauge mode](crate::Knob::with_gauge_mode), other knobs show their value directly.
+    pub fn displayed_value(ctx: &Context, id: Id) -> Option<f32> {
+        ctx.data(|d| d.get_temp::<GaugeState>(id))
+            .map(|gauge| gauge.displayed_value)
+    }
+
+    /// Whether the knob is being dragged
+    pub fn is_dragged(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Value the knob had when the current drag started
+    pub fn drag_start_value(&self) -> Option<f32> {
+        self.drag.map(|drag| drag.start_value)
+    }
+
+    /// Whether the value is being typed in
+    pub fn is_editing(&self) -> bool {
+        self.edit_text.is_some()
+    }
+
+    /// Whether the pointer is pressed on the knob
+    pub fn is_touched(&self) -> bool {
+        self.touched
+    }
+
+    /// Records the start of a drag and returns the captured state while dragging
+    ///
+    /// Must be called before the drag of the current frame is applied to the value.
+    pub(crate) fn track_drag(
+        &mut self,
+        response: &Response,
+        value: f32,
+        raw: f32,
+    ) -> Option<DragState> {
+        if response.drag_started() {
+            self.drag = Some(DragState {
+                start_value: value,
+                start_raw: raw,
+            });
+            self.step_remainder = 0.0;
+        }
+
+        if !response.dragged() {
+            self.drag = None;
+            self.step_remainder = 0.0;
+        }
+        self.drag
+    }
+}
+
+/// Which end of a range knob a drag moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RangeHandle {
+    Low,
+    High,
+    /// Grabbed between the ends, moving the whole range
+    Both,
+}
+
+/// Drag of a range knob, kept from the press until the release
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RangeDrag {
+    pub handle: RangeHandle,
+    /// Drag movement of the low end smaller than one step, carried over to the next frame
+    ///
+    /// Also used for the whole range while both ends move.
+    pub low_remainder: f32,
+    /// Drag movement of the high end smaller than one step, carried over to the next frame
+    pub high_remainder: f32,
+}
+
+impl RangeDrag {
+    /// Picks the handle when a drag starts and returns the drag for as long as it lasts
+    ///
+    /// Changes to the returned drag are kept with [`Self::store`].
+    pub fn track(ui: &Ui, response: &Response, pick: impl FnOnce() -> RangeHandle) -> Option<Self> {
+        if response.drag_started() {
+            let drag = Self {
+                handle: pick(),
+                low_remainder: 0.0,
+                high_remainder: 0.0,
+            };
+            drag.store(ui, response.id);
+        }
+
+        if response.dragged() {
+            ui.data(|d| d.get_temp::<Self>(response.id))
+        } else {
+            if response.drag_stopped() {
+                ui.data_mut(|d| d.remove::<Self>(response.id));
+            }
+            None
+        }
+    }
+
+    pub fn store(self, ui: &Ui, id: Id) {
+        ui.data_mut(|d| d.insert_temp(id, self));
+    }
+}
+
+/// Value a knob left behind in the previous frame
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LastValue(f32);
+
+impl LastValue {
+    /// Whether `incoming` differs from the value left behind in the previous frame, i.e.
+    /// the application changed it, and remembers `value` for the next frame
+    pub fn changed_elsewhere(ui: &Ui, id: Id, incoming: f32, value: f32) -> bool {
+        ui.data_mut(|d| {
+            let last = d.get_temp::<Self>(id);
+            d.insert_temp(id, Self(value));
+            last.is_some_and(|Self(last)| last != incoming)
+        })
+    }
+}
+
+/// Normalized value a knob wrote back to its binding, with the plain value behind it
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NormalizedBinding {
+    normalized: f32,
+    plain: f32,
+}
+
+impl NormalizedBinding {
+    /// Plain value of the bound `normalized` value
+    ///
+    /// While the application leaves the binding alone, this is exactly the plain value of
+    /// the previous frame, so mapping back and forth doesn't drift.
+    pub fn plain(ui: &Ui, id: Id, normalized: f32, denormalize: impl FnOnce(f32) -> f32) -> f32 {
+        match ui.data(|d| d.get_temp::<Self>(id)) {
+            Some(last) if last.normalized == normalized => last.plain,
+            _ => denormalize(normalized),
+        }
+    }
+
+    /// Remembers that `normalized` was written back for `plain`
+    pub fn store(ui: &Ui, id: Id, normalized: f32, plain: f32) {
+        ui.data_mut(|d| d.insert_temp(id, Self { normalized, plain }));
+    }
+}
+
+/// Highlight of a knob whose value was changed outside the widget, as told by [`LastValue`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PulseState {
+    pulse_start: f64,
+}
+
+impl PulseState {
+    /// Returns the current pulse intensity in `0.0..=1.0`
+    ///
+    /// A pulse starts when the application `changed_elsewhere` the value, and fades out
+    /// over `duration` seconds.
+    pub fn update(ui: &Ui, id: Id, changed_elsewhere: bool, duration: f32) -> f32 {
+        let now = ui.input(|i| i.time);
+        let pulse_start = if changed_elsewhere {
+            Some(now)
+        } else {
+            ui.data(|d| d.get_temp::<Self>(id))
+                .map(|state| state.pulse_start)
+        };
+
+        let intensity = pulse_start.map_or(0.0, |start| {
+            1.0 - (now - start) as f32 / duration.max(f32::EPSILON)
+        });
+
+        if let Some(pulse_start) = pulse_start.filter(|_| intensity > 0.0) {
+            ui.ctx().request_repaint();
+            ui.data_mut(|d| d.insert_temp(id, Self { pulse_start }));
+        } else {
+            ui.data_mut(|d| d.remove::<Self>(id));
+        }
+
+        intensity.clamp(0.0, 1.0)
+    }
+}
+
+/// Which part of a jog wheel a drag turns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JogGrab {
+    /// The wheel itself
+    Wheel,
+    /// The shuttle ring around it
+    Shuttle,
+}
+
+/// Drag and spin of a jog wheel between frames
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct JogState {
+    /// Part being dragged, `None` while released
+    pub grab: Option<JogGrab>,
+    /// Pointer angle around the center in the previous frame
+    pub angle: f32,
+    /// Rotation speed in turns per second
+    pub velocity: f32,
+    /// Shuttle deflection in `-1.0..=1.0`
+    pub shuttle: f32,
+}
+
+impl JogState {
+    pub fn load(ctx: &Context, id: Id) -> Self {
+        ctx.data(|d| d.get_temp(id)).unwrap_or_default()
+    }
+
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+}
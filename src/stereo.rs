@@ -0,0 +1,175 @@
+use std::ops::{Deref, DerefMut};
+
+use egui::{Response, Ui, Widget};
+
+use crate::style::{KnobStyle, LabelPosition};
+use crate::widget::Knob;
+
+/// Adjusts both knobs of a pair, e.g. to set colors or formatting
+type ConfigureFn<'a> = Box<dyn Fn(Knob<'_>) -> Knob<'_> + 'a>;
+
+/// How a linked stereo pair follows an edit of either knob
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StereoLink {
+    /// Both knobs jump to the edited value
+    #[default]
+    Absolute,
+    /// Both knobs move by the same amount, keeping their offset
+    Relative,
+}
+
+/// Left and right knobs with a toggle that links them
+///
+/// While linked, an edit of one knob is applied to the other as set by [`StereoLink`].
+/// Unlinked, both are edited independently.
+pub struct StereoKnobs<'a> {
+    left: &'a mut f32,
+    right: &'a mut f32,
+    linked: &'a mut bool,
+    min: f32,
+    max: f32,
+    style: KnobStyle,
+    link: StereoLink,
+    mirrored: bool,
+    configure: Option<ConfigureFn<'a>>,
+}
+
+impl<'a> StereoKnobs<'a> {
+    /// Creates a stereo pair
+    ///
+    /// # Arguments
+    /// * `left` - Value of the left channel
+    /// * `right` - Value of the right channel
+    /// * `linked` - State of the link toggle
+    /// * `min` - Minimum value
+    /// * `max` - Maximum value
+    /// * `style` - Visual style of both knobs
+    pub fn new(
+        left: &'a mut f32,
+        right: &'a mut f32,
+        linked: &'a mut bool,
+        min: f32,
+        max: f32,
+        style: KnobStyle,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            linked,
+            min,
+            max,
+            style,
+            link: StereoLink::default(),
+            mirrored: false,
+            configure: None,
+        }
+    }
+
+    /// Sets how the knobs follow each other while linked
+    pub fn with_link(mut self, link: StereoLink) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// Mirrors the sweep of the left knob, so the pair turns symmetrically
+    ///
+    /// See [`Knob::with_mirrored_sweep`].
+    pub fn with_mirrored_sweep(mut self) -> Self {
+        self.mirrored = true;
+        self
+    }
+
+    /// Applies the same builder calls to both knobs
+    pub fn with_config(mut self, configure: impl Fn(Knob<'_>) -> Knob<'_> + 'a) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+}
+
+impl StereoKnobs<'_> {
+    /// Shows the pair, returning the knobs' response and the link toggle's separately
+    pub fn show(self, ui: &mut Ui) -> StereoResponse {
+        let (left_before, right_before) = (*self.left, *self.right);
+        let (min, max, style) = (self.min, self.max, self.style);
+        let configure = self.configure;
+        let knob = |value: &mut f32, label: &str, mirrored: bool, ui: &mut Ui| {
+            let mut knob =
+                Knob::new(value, min, max, style.clone()).with_label(label, LabelPosition::Bottom);
+            if let Some(configure) = &configure {
+                knob = configure(knob);
+            }
+            if mirrored {
+                knob = knob.with_mirrored_sweep();
+            }
+            ui.add(knob)
+        };
+
+        let inner = ui.horizontal(|ui| {
+            let left = knob(self.left, "L", self.mirrored, ui);
+            let link = ui
+                .toggle_value(self.linked, "Link")
+                .on_hover_text("Apply edits to both channels");
+            let right = knob(self.right, "R", false, ui);
+            (left, link, right)
+        });
+        let (left, link, right) = inner.inner;
+
+        if *self.linked {
+            let (low, high) = (min.min(max), min.max(max));
+            let follow = |edited: f32, edited_before: f32, other: &mut f32| {
+                *other = match self.link {
+                    StereoLink::Absolute => edited,
+                    StereoLink::Relative => (*other + edited - edited_before).clamp(low, high),
+                };
+            };
+            if left.changed() {
+                follow(*self.left, left_before, self.right);
+            } else if right.changed() {
+                follow(*self.right, right_before, self.left);
+            }
+        }
+
+        let mut response = left | right;
+        if *self.left != left_before || *self.right != right_before {
+            response.mark_changed();
+        }
+        StereoResponse { response, link }
+    }
+}
+
+impl Widget for StereoKnobs<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// What happened to a [`StereoKnobs`] pair this frame
+///
+/// Derefs to the response of the two knobs.
+#[derive(Debug, Clone)]
+pub struct StereoResponse {
+    /// Union of both knobs' responses, changed when either value changed
+    pub response: Response,
+    /// Response of the link toggle, changed when it was switched
+    pub link: Response,
+}
+
+impl Deref for StereoResponse {
+    type Target = Response;
+
+    fn deref(&self) -> &Response {
+        &self.response
+    }
+}
+
+impl DerefMut for StereoResponse {
+    fn deref_mut(&mut self) -> &mut Response {
+        &mut self.response
+    }
+}
+
+impl From<StereoResponse> for Response {
+    fn from(response: StereoResponse) -> Self {
+        response.response
+    }
+}
@@ -1,16 +1,138 @@
-use egui::Color32;
+use std::sync::Arc;
+
+use egui::{Color32, Context, FontId, Id, Modifiers, Painter, Pos2, Stroke, Ui, Vec2, Visuals};
 
 /// Visual style of the knob indicator
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KnobStyle {
     /// A line extending from the center to the edge
     Wiper,
     /// A dot on the edge of the knob
     Dot,
+    /// A smaller cap on a larger skirt, with the pointer line drawn on the skirt
+    Skirted,
+    /// Two wipers mirrored about the middle of the sweep, spreading apart as the value
+    /// increases, with the fill spanning between them
+    ///
+    /// Visualizes stereo width or mid/side amounts. Pair it with a sweep centered on
+    /// 12 o'clock, e.g. `with_angle_range_degrees(-135.0, 135.0)`, so the wipers start
+    /// together at the top.
+    Spread,
+    /// An indicator drawn by a [`KnobIndicator`] implementation
+    ///
+    /// Skipped by serde, since the implementation can't be serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<dyn KnobIndicator>),
 }
 
-/// Position of the label relative to the knob
+impl KnobStyle {
+    /// Creates a style that draws the indicator with `indicator`
+    pub fn custom(indicator: impl KnobIndicator + 'static) -> Self {
+        Self::Custom(Arc::new(indicator))
+    }
+}
+
+impl std::fmt::Debug for KnobStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wiper => f.write_str("Wiper"),
+            Self::Dot => f.write_str("Dot"),
+            Self::Skirted => f.write_str("Skirted"),
+            Self::Spread => f.write_str("Spread"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for KnobStyle {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for KnobStyle {}
+
+/// Draws the pointer of a knob, for indicator styles beyond the built-in ones
+///
+/// Used through [`KnobStyle::Custom`]. The knob body, arcs and ticks are still drawn
+/// by the knob; only the pointer for the current value is delegated.
+///
+/// ```
+/// use egui_knob::egui::{Painter, Pos2, Stroke, Vec2};
+/// use egui_knob::{KnobIndicator, KnobStyle};
+///
+/// struct Notch;
+///
+/// impl KnobIndicator for Notch {
+///     fn paint(&self, painter: &Painter, center: Pos2, radius: f32, angle: f32, stroke: Stroke) {
+///         let direction = Vec2::angled(angle);
+///         let notch = [center + direction * radius * 0.8, center + direction * radius];
+///         painter.line_segment(notch, stroke);
+///     }
+/// }
+///
+/// let style = KnobStyle::custom(Notch);
+/// ```
+pub trait KnobIndicator: Send + Sync {
+    /// Paints the indicator
+    ///
+    /// # Arguments
+    /// * `painter` - Painter to draw with
+    /// * `center` - Center of the knob
+    /// * `radius` - Radius of the knob body
+    /// * `angle` - Angle of the current value in radians, as used by [`Vec2::angled`]
+    /// * `stroke` - Stroke width and indicator color configured on the knob
+    fn paint(&self, painter: &Painter, center: Pos2, radius: f32, angle: f32, stroke: Stroke);
+}
+
+/// Named knob sizes with matching stroke width and font size
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KnobSize {
+    /// Compact knob for dense banks
+    Small,
+    /// The default size
+    Medium,
+    /// Prominent knob for main controls
+    Large,
+}
+
+impl KnobSize {
+    /// Knob diameter in points
+    pub fn diameter(self) -> f32 {
+        match self {
+            Self::Small => 28.0,
+            Self::Medium => 40.0,
+            Self::Large => 64.0,
+        }
+    }
+
+    /// Width of outline, arcs and indicator, which also sets the dot size
+    pub fn stroke_width(self) -> f32 {
+        match self {
+            Self::Small => 1.5,
+            Self::Medium => 2.0,
+            Self::Large => 3.0,
+        }
+    }
+
+    /// Font size of the labels
+    pub fn font_size(self) -> f32 {
+        match self {
+            Self::Small => 10.0,
+            Self::Medium => 12.0,
+            Self::Large => 14.0,
+        }
+    }
+}
+
+/// Position of the label relative to the knob
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LabelPosition {
     /// Label appears above the knob
     Top,
@@ -20,10 +142,117 @@ pub enum LabelPosition {
     Left,
     /// Label appears to the right of the knob
     Right,
+    /// Label appears over the center of the knob
+    Center,
+    /// Label is centered at the given offset from the knob center
+    ///
+    /// No extra space is allocated for it, e.g. to place the label in the lower arc gap.
+    Custom(Vec2),
+}
+
+impl LabelPosition {
+    pub(crate) const ALL: [Self; 5] = [
+        Self::Top,
+        Self::Bottom,
+        Self::Left,
+        Self::Right,
+        Self::Center,
+    ];
+}
+
+/// Placement of the value arc relative to the knob body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArcPlacement {
+    /// Arc is drawn inside the knob outline
+    Inside,
+    /// Arc is drawn as a ring around the knob body, like an LED ring on an encoder
+    Outside,
+}
+
+/// Where the change relative to the drag-start value is shown while dragging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeltaReadout {
+    /// Delta appears in the popup next to the pointer
+    Popup,
+    /// Delta replaces the value in the knob's label
+    Label,
+}
+
+/// Builds custom tooltip content from the current value
+pub type TooltipContent = Arc<dyn Fn(&mut Ui, f32) + Send + Sync>;
+
+/// Content of the tooltip shown while hovering the knob
+#[derive(Clone)]
+pub enum TooltipMode {
+    /// No hover tooltip
+    Off,
+    /// Formatted value, shown when the knob has a label
+    Value,
+    /// Formatted value, shown with or without a label
+    ///
+    /// Suits compact unlabeled knobs, whose value is otherwise not visible at all.
+    AlwaysValue,
+    /// Formatted value, shown only while the knob is dragged
+    WhileDragging,
+    /// Custom content, built from the current value
+    Custom(TooltipContent),
+}
+
+impl std::fmt::Debug for TooltipMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => f.write_str("Off"),
+            Self::Value => f.write_str("Value"),
+            Self::AlwaysValue => f.write_str("AlwaysValue"),
+            Self::WhileDragging => f.write_str("WhileDragging"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// How the user invokes an action of a knob, like MIDI learn or the value editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvokeGesture {
+    /// An entry in the knob's context menu
+    ContextMenu,
+    /// A click while holding exactly these modifiers, e.g. `Modifiers::CTRL | Modifiers::ALT`
+    Click(Modifiers),
+}
+
+/// What a knob does with a bound value outside of its range
+///
+/// The indicator is drawn at the nearest end of the sweep in every case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutOfRange {
+    /// Clamps the value into the range as soon as the knob sees it
+    Clamp,
+    /// Leaves the value alone until the user changes it
+    #[default]
+    ClampOnInteraction,
+    /// Like [`OutOfRange::ClampOnInteraction`], with a warning mark at the exceeded end
+    Indicate,
+}
+
+/// Value a knob puts in place of a bound value that is NaN
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NanFallback {
+    /// The start of the range
+    #[default]
+    Min,
+    /// The value set with [`Knob::with_default`](crate::Knob::with_default), or the start
+    /// of the range without one
+    Default,
+    /// This value, clamped to the range
+    Value(f32),
 }
 
 /// Color configuration for the knob widget
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KnobColors {
     /// Color of the knob's outline
     pub knob_color: Color32,
@@ -31,14 +260,206 @@ pub struct KnobColors {
     pub line_color: Color32,
     /// Color of the label text
     pub text_color: Color32,
+    /// Color of the value text when shown as a separate label
+    pub value_text_color: Color32,
+    /// Color of the background arc showing the full range
+    pub arc_background_color: Color32,
+    /// Color of the filled arc segment up to the current value
+    pub arc_fill_color: Color32,
+    /// Fill color of the knob cap
+    pub cap_fill_color: Color32,
+    /// Color of tick marks
+    pub tick_color: Color32,
+    /// Color of the knob's outline while hovered
+    pub hover_color: Color32,
+    /// Color of the embedded level meter
+    pub meter_color: Color32,
+}
+
+impl KnobColors {
+    /// Creates a color set from the three base colors, deriving the remaining ones
+    ///
+    /// # Arguments
+    /// * `knob_color` - Color of the knob's outline
+    /// * `line_color` - Color of the indicator and the filled arc
+    /// * `text_color` - Color of the label text
+    pub fn from_base(knob_color: Color32, line_color: Color32, text_color: Color32) -> Self {
+        Self {
+            knob_color,
+            line_color,
+            text_color,
+            value_text_color: text_color,
+            arc_background_color: knob_color.gamma_multiply(0.35),
+            arc_fill_color: line_color,
+            cap_fill_color: knob_color.gamma_multiply(0.15),
+            tick_color: knob_color,
+            hover_color: knob_color.linear_multiply(1.15),
+            meter_color: line_color.gamma_multiply(0.7),
+        }
+    }
+
+    /// Creates a color set matching the given egui visuals
+    ///
+    /// Body and outline follow the inactive widget style, the hover color the hovered one,
+    /// and the indicator uses the selection accent like egui's sliders.
+    pub fn from_visuals(visuals: &Visuals) -> Self {
+        let widgets = &visuals.widgets;
+        Self {
+            arc_background_color: widgets.inactive.bg_fill,
+            cap_fill_color: widgets.inactive.weak_bg_fill,
+            hover_color: widgets.hovered.fg_stroke.color,
+            ..Self::from_base(
+                widgets.inactive.fg_stroke.color,
+                visuals.selection.bg_fill,
+                visuals.text_color(),
+            )
+        }
+    }
 }
 
 impl Default for KnobColors {
     fn default() -> Self {
+        Self::from_base(Color32::GRAY, Color32::GRAY, Color32::WHITE)
+    }
+}
+
+/// A complete look for knobs: colors, line widths, proportions and fonts
+///
+/// Apply it with [`Knob::with_theme`](crate::Knob::with_theme). The presets cover common
+/// looks and can be adjusted field by field.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KnobTheme {
+    pub colors: KnobColors,
+    /// Width of outline, arcs and indicator
+    pub stroke_width: f32,
+    /// Fraction of the knob's square that is painted
+    pub visual_scale: f32,
+    /// Whether the outline circle is drawn
+    pub show_outline: bool,
+    /// Font of the name label
+    pub font: FontId,
+    /// Font of a separate value label, `None` to use the name font
+    pub value_font: Option<FontId>,
+}
+
+impl KnobTheme {
+    /// Gray body with a light blue indicator, for dark backgrounds
+    pub fn dark() -> Self {
+        Self::from_colors(KnobColors::from_base(
+            Color32::from_gray(80),
+            Color32::from_rgb(90, 170, 255),
+            Color32::from_gray(220),
+        ))
+    }
+
+    /// Dark outline with a blue indicator, for light backgrounds
+    pub fn light() -> Self {
+        Self::from_colors(KnobColors {
+            cap_fill_color: Color32::from_gray(235),
+            ..KnobColors::from_base(
+                Color32::from_gray(120),
+                Color32::from_rgb(30, 110, 220),
+                Color32::from_gray(30),
+            )
+        })
+    }
+
+    /// Brown body with cream markings, like old hardware
+    pub fn vintage() -> Self {
         Self {
-            knob_color: Color32::GRAY,
-            line_color: Color32::GRAY,
-            text_color: Color32::WHITE,
+            stroke_width: 2.5,
+            ..Self::from_colors(KnobColors {
+                cap_fill_color: Color32::from_rgb(60, 40, 28),
+                ..KnobColors::from_base(
+                    Color32::from_rgb(120, 85, 55),
+                    Color32::from_rgb(235, 210, 160),
+                    Color32::from_rgb(240, 225, 190),
+                )
+            })
         }
     }
+
+    /// Thin arc and indicator without body or outline
+    pub fn flat() -> Self {
+        Self {
+            stroke_width: 1.5,
+            show_outline: false,
+            ..Self::from_colors(KnobColors {
+                cap_fill_color: Color32::TRANSPARENT,
+                ..KnobColors::from_base(
+                    Color32::from_gray(140),
+                    Color32::from_rgb(0, 170, 150),
+                    Color32::from_gray(200),
+                )
+            })
+        }
+    }
+
+    /// Bold white and yellow markings on black, with thick strokes and large text
+    ///
+    /// For users with low vision. The indicator and [`LabelPosition::Center`] text contrast
+    /// with the black cap by more than 7:1, and the unfilled arc still by more than 3:1,
+    /// meeting WCAG AAA for text and the non-text contrast minimum for the arc. Labels
+    /// outside the knob are drawn on the panel instead, so their contrast is only as good
+    /// as the panel is dark; pair the theme with dark visuals. Apply it to single knobs or
+    /// install it for the whole app.
+    pub fn high_contrast() -> Self {
+        let accent = Color32::from_rgb(255, 214, 0);
+        Self {
+            stroke_width: 3.5,
+            font: FontId::proportional(16.0),
+            ..Self::from_colors(KnobColors {
+                knob_color: Color32::WHITE,
+                line_color: accent,
+                text_color: Color32::WHITE,
+                value_text_color: accent,
+                arc_background_color: Color32::from_gray(120),
+                arc_fill_color: accent,
+                cap_fill_color: Color32::BLACK,
+                tick_color: Color32::WHITE,
+                hover_color: accent,
+                meter_color: Color32::from_rgb(0, 220, 255),
+            })
+        }
+    }
+
+    /// Makes this the default look of every knob in `ctx`
+    ///
+    /// Knobs still apply their own color, stroke and font settings on top of it.
+    pub fn install(&self, ctx: &Context) {
+        ctx.data_mut(|d| d.insert_temp(Self::id(), self.clone()));
+    }
+
+    /// Removes the theme installed with [`KnobTheme::install`]
+    pub fn uninstall(ctx: &Context) {
+        ctx.data_mut(|d| d.remove::<Self>(Self::id()));
+    }
+
+    /// The theme installed in `ctx`, if any
+    pub fn installed(ctx: &Context) -> Option<Self> {
+        ctx.data(|d| d.get_temp(Self::id()))
+    }
+
+    fn id() -> Id {
+        Id::new("egui_knob_theme")
+    }
+
+    /// A theme with the given colors and default sizes and fonts
+    pub fn from_colors(colors: KnobColors) -> Self {
+        Self {
+            colors,
+            stroke_width: 2.0,
+            visual_scale: 1.0,
+            show_outline: true,
+            font: FontId::proportional(12.0),
+            value_font: None,
+        }
+    }
+}
+
+impl Default for KnobTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
 }
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use egui::{Modifiers, Response, Ui, Widget};
+
+use crate::config::KnobConfig;
+use crate::formatters::Formatter;
+use crate::style::KnobStyle;
+use crate::widget::Knob;
+
+/// A knob with fixed, named positions, like a hardware rotary switch
+///
+/// Dragging, scrolling and the arrow keys move from position to position without stopping
+/// in between. Each position gets a tick mark, and the selected name is shown as the value.
+///
+/// ```
+/// # fn demo(ui: &mut egui_knob::egui::Ui) {
+/// use egui_knob::{KnobConfig, KnobStyle, LabelPosition, RotarySwitch};
+///
+/// let mut mode = 0;
+/// ui.add(
+///     RotarySwitch::new(&mut mode, ["Sine", "Saw", "Square"], KnobStyle::Skirted)
+///         .with_config(KnobConfig::default().with_label("Wave", LabelPosition::Bottom))
+///         .with_click_to_advance(true),
+/// );
+/// # }
+/// ```
+pub struct RotarySwitch<'a> {
+    selected: &'a mut usize,
+    positions: Vec<String>,
+    config: KnobConfig,
+    click_to_advance: bool,
+}
+
+impl<'a> RotarySwitch<'a> {
+    /// Creates a rotary switch
+    ///
+    /// # Arguments
+    /// * `selected` - Index of the selected position
+    /// * `positions` - Names of the positions, in order along the sweep. A single position
+    ///   is shown selected in the middle of the sweep and can't be turned.
+    /// * `style` - Visual style of the indicator
+    pub fn new(
+        selected: &'a mut usize,
+        positions: impl IntoIterator<Item = impl Into<String>>,
+        style: KnobStyle,
+    ) -> Self {
+        Self {
+            selected,
+            positions: positions.into_iter().map(Into::into).collect(),
+            config: KnobConfig::new(style),
+            click_to_advance: false,
+        }
+    }
+
+    /// Sets the look of the switch, e.g. its label, size and sweep
+    ///
+    /// The style passed to [`RotarySwitch::new`] is kept. The range, steps, tick marks and
+    /// value format follow from the positions, so those settings of `config` are replaced.
+    pub fn with_config(mut self, config: KnobConfig) -> Self {
+        let style = self.config.style.clone();
+        self.config = config.with_style(style);
+        self
+    }
+
+    /// Moves to the next position on a click and to the previous one on a Shift-click
+    ///
+    /// Both wrap around at the ends, like a selector cycling through its modes.
+    pub fn with_click_to_advance(mut self, enabled: bool) -> Self {
+        self.click_to_advance = enabled;
+        self
+    }
+}
+
+impl Widget for RotarySwitch<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let count = self.positions.len();
+        let last = count.saturating_sub(1);
+        let before = *self.selected;
+        *self.selected = before.min(last);
+
+        let names = Arc::new(self.positions);
+        let format = {
+            let names = Arc::clone(&names);
+            move |value: f32| {
+                names
+                    .get(value.round() as usize)
+                    .cloned()
+                    .unwrap_or_default()
+            }
+        };
+        let parse = move |text: &str| {
+            let text = text.trim();
+            names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(text))
+                .map(|index| index as f32)
+        };
+        let mut config = self
+            .config
+            .with_formatter(Formatter::new(format, parse))
+            .with_step_by(1.0)
+            .with_tick_marks(count);
+        config.logarithmic_scaling = false;
+        config.normalized_value = false;
+        // A single position can't span a range, so it's drawn as a fixed detent selected
+        // in the middle of the sweep, shown like any other switch but not turning
+        let (min, max) = if count > 1 {
+            (0.0, last as f32)
+        } else {
+            config = config.with_gauge_mode(0.0);
+            (-1.0, 1.0)
+        };
+
+        let mut value = *self.selected as f32;
+        let mut response = ui.add(Knob::from_config(&mut value, min, max, config));
+        *self.selected = (value.round() as usize).min(last);
+
+        if self.click_to_advance && count > 1 && response.clicked() {
+            let modifiers = ui.input(|i| i.modifiers);
+            if modifiers.is_none() {
+                *self.selected = (*self.selected + 1) % count;
+            } else if modifiers.matches_exact(Modifiers::SHIFT) {
+                *self.selected = (*self.selected + last) % count;
+            }
+        }
+
+        if *self.selected != before {
+            response.mark_changed();
+        }
+        response
+    }
+}
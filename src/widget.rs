@@ -1,16 +1,53 @@
-use egui::{remap, Color32, Response, Sense, Ui, Widget};
+use egui::accesskit::{Action, ActionData};
+use egui::{
+    Align, Align2, Button, Color32, Event, EventFilter, FontId, Id, Key, Modifiers, Rect, Response,
+    Sense, Ui, Visuals, Widget, WidgetInfo,
+};
 
-use crate::config::KnobConfig;
+use crate::config::{KnobAppearance, KnobConfig, KnobPreset};
+use crate::editor::{self, EditorOutcome};
+use crate::format::{NumberLocale, TimeUnit};
+use crate::formatters::Formatter;
+use crate::gesture::{ParamGestureHandler, ValueEdit};
+use crate::math;
 use crate::render::KnobRenderer;
-use crate::style::{KnobStyle, LabelPosition};
+use crate::response::{ChangeCause, KnobResponse};
+use crate::state::{
+    GaugeState, KnobGeometry, KnobState, LastValue, NormalizedBinding, PeakState, PulseState,
+    ScrollBurst, ValueAnimation,
+};
+use crate::style::{
+    ArcPlacement, DeltaReadout, InvokeGesture, KnobColors, KnobSize, KnobStyle, KnobTheme,
+    LabelPosition, NanFallback, OutOfRange, TooltipMode,
+};
+
+/// Normalized change per arrow key or increment from assistive technology, without a step
+const ACCESSIBLE_STEP: f32 = 0.01;
+
+/// Key of the last value copied from any knob, for the context menu's "Paste" entry
+///
+/// egui can't read the system clipboard outside of paste events, so the menu pastes
+/// from here instead.
+const CLIPBOARD_ID: &str = "egui_knob_clipboard";
+
+/// Pause in seconds after which further scrolling counts as a new edit for the undo hook
+const SCROLL_BURST_GAP: f64 = 0.5;
 
 pub struct Knob<'a> {
     pub(crate) value: &'a mut f32,
     pub(crate) min: f32,
     pub(crate) max: f32,
     pub(crate) config: KnobConfig,
+    pub(crate) gestures: Option<Box<dyn ParamGestureHandler + 'a>>,
+    pub(crate) learn: Option<(InvokeGesture, Box<dyn FnMut() + 'a>)>,
+    pub(crate) undo: Option<Box<dyn FnMut(ValueEdit) + 'a>>,
+    pub(crate) animation: Option<(f32, f32)>,
+    pub(crate) touch: Option<TouchCallbacks<'a>>,
 }
 
+/// Called when the pointer is pressed on a knob and when it's released again
+pub(crate) type TouchCallbacks<'a> = (Box<dyn FnMut() + 'a>, Box<dyn FnMut() + 'a>);
+
 impl<'a> Knob<'a> {
     /// Creates a new knob widget
     ///
@@ -20,14 +57,46 @@ impl<'a> Knob<'a> {
     /// * `max` - Maximum value
     /// * `style` - Visual style of the knob indicator
     pub fn new(value: &'a mut f32, min: f32, max: f32, style: KnobStyle) -> Self {
+        Self::from_config(value, min, max, KnobConfig::new(style))
+    }
+
+    /// Creates a knob with a prepared configuration
+    ///
+    /// # Arguments
+    /// * `value` - Mutable reference to the value controlled by the knob
+    /// * `min` - Minimum value
+    /// * `max` - Maximum value
+    /// * `config` - Appearance and behavior, built once with the [`KnobConfig`] setters
+    pub fn from_config(value: &'a mut f32, min: f32, max: f32, config: KnobConfig) -> Self {
         Self {
             value,
             min,
             max,
-            config: KnobConfig::new(style),
+            config,
+            gestures: None,
+            learn: None,
+            undo: None,
+            animation: None,
+            touch: None,
         }
     }
 
+    /// Replaces the whole configuration with the one stored in `preset`
+    ///
+    /// Builder calls after this one adjust the knob on top of the preset, e.g. to set its label.
+    pub fn with_preset(mut self, preset: &KnobPreset) -> Self {
+        self.config = preset.config().clone();
+        self
+    }
+
+    /// Applies saved visual settings, e.g. loaded from the app's settings file
+    ///
+    /// Labels, formatting and behavior are kept.
+    pub fn with_appearance(mut self, appearance: KnobAppearance) -> Self {
+        self.config = self.config.with_appearance(appearance);
+        self
+    }
+
     /// Sets the angular sweep range of the knob
     ///
     /// This controls where the knob starts and how far it can rotate. By default,
@@ -44,41 +113,127 @@ impl<'a> Knob<'a> {
     ///   - `0.5` = half turn (180°)
     ///   - `0.75` = three-quarter turn (270°)
     ///   - `1.0` = full turn (360°)
-    ///   - Values > 1.0 create multi-turn knobs
-    ///   - Negative values are clamped to 0.0
+    ///   - Values > 1.0 create multi-turn knobs, whose arcs are drawn as a spiral
+    ///   - Negative values sweep counter-clockwise, so `with_sweep_range(0.75, -0.75)`
+    ///     mirrors the default sweep, e.g. for the left knob of a stereo pair
     ///
     /// Note: the start angle is offset by PI/2 so that `0.0` is at the bottom (6 o'clock)
     pub fn with_sweep_range(mut self, start_angle_normalized: f32, range: f32) -> Self {
-        if start_angle_normalized.is_nan() || range.is_nan() {
-            return self;
-        }
+        self.config = self.config.with_sweep_range(start_angle_normalized, range);
+        self
+    }
+
+    /// Sets the angular sweep from one clock position to another, in degrees
+    ///
+    /// Angles are measured clockwise from 12 o'clock, so the classic 7 to 5 o'clock
+    /// sweep is `with_angle_range_degrees(-135.0, 135.0)`. An alternative to
+    /// [`Self::with_sweep_range`], whichever was called last wins.
+    ///
+    /// # Arguments
+    /// * `start_deg` - Position of the minimum value:
+    ///   - `0.0` = top (12 o'clock)
+    ///   - `90.0` = right (3 o'clock)
+    ///   - `180.0` = bottom (6 o'clock)
+    ///   - `-90.0` or `270.0` = left (9 o'clock)
+    /// * `end_deg` - Position of the maximum value, on the same scale. The sweep is
+    ///   `end_deg - start_deg`, so values more than 360° past the start create
+    ///   multi-turn knobs. Ends before the start sweep counter-clockwise
+    pub fn with_angle_range_degrees(mut self, start_deg: f32, end_deg: f32) -> Self {
+        self.config = self.config.with_angle_range_degrees(start_deg, end_deg);
+        self
+    }
 
-        self.config.min_angle = 
-            start_angle_normalized.rem_euclid(1.0) * std::f32::consts::TAU + std::f32::consts::PI / 2.0;
-        self.config.max_angle = self.config.min_angle + range.max(0.0) * std::f32::consts::TAU;
+    /// Same as [`Self::with_angle_range_degrees`], in radians
+    pub fn with_angle_range_radians(mut self, start: f32, end: f32) -> Self {
+        self.config = self.config.with_angle_range_radians(start, end);
+        self
+    }
+
+    /// Mirrors the sweep left to right, reversing its direction
+    ///
+    /// Applies to the sweep set so far, so call it after [`Self::with_sweep_range`] or
+    /// [`Self::with_angle_range_degrees`]. The default sweep then runs counter-clockwise
+    /// from 3 o'clock to 6 o'clock, as on the left knob of a mirrored channel pair.
+    pub fn with_mirrored_sweep(mut self) -> Self {
+        self.config = self.config.with_mirrored_sweep();
         self
     }
 
     /// Sets the size of the knob
+    ///
+    /// Without an explicit size the diameter follows `ui.spacing().interact_size`,
+    /// so knobs scale with the app's spacing settings.
     pub fn with_size(mut self, size: f32) -> Self {
-        self.config.size = size;
+        self.config = self.config.with_size(size);
+        self
+    }
+
+    /// Applies a named size preset to diameter, stroke width and font size
+    ///
+    /// Later calls to [`Knob::with_size`], [`Knob::with_stroke_width`] or
+    /// [`Knob::with_font_size`] override the respective part of the preset.
+    pub fn with_size_preset(mut self, preset: KnobSize) -> Self {
+        self.config = self.config.with_size_preset(preset);
+        self
+    }
+
+    /// Derives the knob size from the available width instead of a fixed size
+    ///
+    /// Replaces any size set with [`Knob::with_size`].
+    ///
+    /// # Arguments
+    /// * `fraction` - Fraction of the available width used as the knob diameter
+    /// * `min` - Smallest allowed diameter
+    /// * `max` - Largest allowed diameter
+    pub fn with_relative_size(mut self, fraction: f32, min: f32, max: f32) -> Self {
+        self.config = self.config.with_relative_size(fraction, min, max);
+        self
+    }
+
+    /// Sets the fraction of the knob's square that is actually painted
+    ///
+    /// The remaining space acts as padding while still responding to the pointer, so dense
+    /// grids of knobs get some air without shrinking the hit area. Default is `1.0`.
+    pub fn with_visual_scale(mut self, scale: f32) -> Self {
+        self.config = self.config.with_visual_scale(scale);
         self
     }
 
     /// Sets the font size for the label
     pub fn with_font_size(mut self, size: f32) -> Self {
-        self.config.font_size = size;
+        self.config = self.config.with_font_size(size);
+        self
+    }
+
+    /// Sets the font size as a fraction of the knob size
+    ///
+    /// The text then scales along with [`Knob::with_size`], so one configuration can be
+    /// reused at different sizes. Replaces any fixed size from [`Knob::with_font_size`].
+    pub fn with_relative_font(mut self, ratio: f32) -> Self {
+        self.config = self.config.with_relative_font(ratio);
+        self
+    }
+
+    /// Sets the font for the label
+    ///
+    /// Allows monospace or custom font families registered with egui.
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.config = self.config.with_font(font);
         self
     }
 
     /// Sets the stroke width for the knob's outline and indicator
     pub fn with_stroke_width(mut self, width: f32) -> Self {
-        self.config.stroke_width = width;
+        self.config = self.config.with_stroke_width(width);
         self
     }
 
     /// Sets the colors for different parts of the knob
     ///
+    /// The arc, cap and hover colors are derived from these.
+    /// Use [`Knob::with_knob_colors`] to set every color explicitly.
+    /// Without any colors set, the knob follows `ui.visuals()`.
+    ///
     /// # Arguments
     /// * `knob_color` - Color of the knob's outline
     /// * `line_color` - Color of the indicator
@@ -89,9 +244,29 @@ impl<'a> Knob<'a> {
         line_color: Color32,
         text_color: Color32,
     ) -> Self {
-        self.config.colors.knob_color = knob_color;
-        self.config.colors.line_color = line_color;
-        self.config.colors.text_color = text_color;
+        self.config = self.config.with_colors(knob_color, line_color, text_color);
+        self
+    }
+
+    /// Sets the full color configuration of the knob
+    pub fn with_knob_colors(mut self, colors: KnobColors) -> Self {
+        self.config = self.config.with_knob_colors(colors);
+        self
+    }
+
+    /// Applies a flat, minimal look: thin arc, small dot and no body
+    ///
+    /// Colors are taken from the given visuals, usually `ui.visuals()`.
+    pub fn with_flat_preset(mut self, visuals: &Visuals) -> Self {
+        self.config = self.config.with_flat_preset(visuals);
+        self
+    }
+
+    /// Applies a complete look: colors, line widths, proportions and fonts
+    ///
+    /// Replaces earlier color, stroke and font settings; later builder calls adjust it further.
+    pub fn with_theme(mut self, theme: &KnobTheme) -> Self {
+        self.config = self.config.with_theme(theme);
         self
     }
 
@@ -101,41 +276,292 @@ impl<'a> Knob<'a> {
     /// * `label` - Text to display
     /// * `position` - Position of the label relative to the knob
     pub fn with_label(mut self, label: impl Into<String>, position: LabelPosition) -> Self {
-        self.config.label = Some(label.into());
-        self.config.label_position = position;
+        self.config = self.config.with_label(label, position);
+        self
+    }
+
+    /// Shows the value as a separate label at `position`
+    ///
+    /// By default the value is appended to the name, as in `"Name: value"`. With a separate
+    /// value label the name can sit above the knob and the value below or inside it.
+    /// Works without a name label too.
+    pub fn with_value_position(mut self, position: LabelPosition) -> Self {
+        self.config = self.config.with_value_position(position);
+        self
+    }
+
+    /// Sets the font of the separate value label
+    ///
+    /// Defaults to the label font.
+    pub fn with_value_font(mut self, font: FontId) -> Self {
+        self.config = self.config.with_value_font(font);
+        self
+    }
+
+    /// Controls whether the value is displayed next to the knob
+    ///
+    /// When disabled only the name is shown and the value is still available on hover.
+    /// To show only the value, use [`Knob::with_value_position`] without a name label.
+    pub fn with_show_value(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_show_value(enabled);
+        self
+    }
+
+    /// Renders the value with a fixed width so the label doesn't wobble while dragging
+    ///
+    /// The value is drawn in the monospace font and padded to the longest formatted value of
+    /// the range. Without a separate value label this applies to the whole label.
+    pub fn with_fixed_width_value(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_fixed_width_value(enabled);
+        self
+    }
+
+    /// Reserves label space for `text` as well as for the formatted ends of the range
+    ///
+    /// The widget size never depends on the current value. Pass the widest string a custom
+    /// format can produce, e.g. `"-100.0 dB"` or `"C#-1"`, so the reserved space covers it
+    /// and neighbouring widgets don't shift or overlap while dragging.
+    pub fn with_widest_value(mut self, text: impl Into<String>) -> Self {
+        self.config = self.config.with_widest_value(text);
+        self
+    }
+
+    /// Sets what the hover tooltip shows
+    ///
+    /// Defaults to [`TooltipMode::Value`], which needs a label. Use
+    /// [`TooltipMode::AlwaysValue`] to show the value on hover for unlabeled knobs too.
+    pub fn with_tooltip(mut self, mode: TooltipMode) -> Self {
+        self.config = self.config.with_tooltip(mode);
+        self
+    }
+
+    /// Draws no text next to the knob and shows name and value in the hover tooltip instead
+    ///
+    /// Useful for compact knob matrices where any text would overflow.
+    pub fn with_tooltip_only_labels(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_tooltip_only_labels(enabled);
+        self
+    }
+
+    /// Hides the labels when the knob is smaller than `min_size`
+    ///
+    /// Name and value move to the hover tooltip instead, the same as with
+    /// [`Knob::with_tooltip_only_labels`], so shrinking layouts don't end up with overlapping text.
+    pub fn with_label_min_size(mut self, min_size: f32) -> Self {
+        self.config = self.config.with_label_min_size(min_size);
+        self
+    }
+
+    /// Sets the font size of the separate value label
+    ///
+    /// Defaults to the label font size.
+    pub fn with_value_font_size(mut self, size: f32) -> Self {
+        self.config = self.config.with_value_font_size(size);
+        self
+    }
+
+    /// Truncates the label with an ellipsis when it is wider than `max_width`
+    ///
+    /// The full text is shown in the hover tooltip when truncated.
+    pub fn with_label_max_width(mut self, max_width: f32) -> Self {
+        self.config = self.config.with_label_max_width(max_width);
+        self
+    }
+
+    /// Wraps the label onto multiple lines when it is wider than `wrap_width`
+    ///
+    /// Lets longer parameter names stack under the knob instead of stretching the layout.
+    /// Takes precedence over [`Knob::with_label_max_width`].
+    pub fn with_label_wrap_width(mut self, wrap_width: f32) -> Self {
+        self.config = self.config.with_label_wrap_width(wrap_width);
+        self
+    }
+
+    /// Sets where the knob sits horizontally when top or bottom labels are wider than it
+    ///
+    /// The labels follow the knob: left-aligned for [`Align::Min`], right-aligned for
+    /// [`Align::Max`]. Defaults to [`Align::Center`].
+    pub fn with_knob_align(mut self, align: Align) -> Self {
+        self.config = self.config.with_knob_align(align);
         self
     }
 
     /// Sets the spacing between the knob and its label
     pub fn with_label_offset(mut self, offset: f32) -> Self {
-        self.config.label_offset = offset;
+        self.config = self.config.with_label_offset(offset);
         self
     }
 
     /// Sets a custom format function for displaying the value
     ///
+    /// Without a custom format, the number of decimals is derived from the step size, or
+    /// from the span of the range when no step is set.
+    ///
     /// # Example
     /// ```no_run
     /// use egui_knob::{Knob, KnobStyle};
+    /// # fn demo(ui: &mut egui_knob::egui::Ui) {
+    /// # let mut value = 0.5;
     /// ui.add(
     ///     Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
     ///         .with_label_format(|v| format!("{:.1}%", v * 100.0))
     /// );
+    /// # }
+    /// ```
+    pub fn with_label_format(
+        mut self,
+        format: impl Fn(f32) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.config = self.config.with_label_format(format);
+        self
+    }
+
+    /// Sets a function that converts typed text back into a value
+    ///
+    /// Should be the inverse of the label format, e.g. turning `"50%"` into `0.5`. The
+    /// default parser accepts plain numbers with an optional SI prefix and the unit, like
+    /// `"2k"` or `"1.5 kHz"`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use egui_knob::{Knob, KnobStyle};
+    /// # fn demo(ui: &mut egui_knob::egui::Ui) {
+    /// # let mut value = 0.5;
+    /// ui.add(
+    ///     Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+    ///         .with_label_format(|v| format!("{:.0}%", v * 100.0))
+    ///         .with_custom_parser(|s| {
+    ///             let percent: f32 = s.trim_end_matches('%').trim().parse().ok()?;
+    ///             Some(percent / 100.0)
+    ///         })
+    /// );
+    /// # }
     /// ```
-    pub fn with_label_format(mut self, format: impl Fn(f32) -> String + 'static) -> Self {
-        self.config.label_format = Box::new(format);
+    pub fn with_custom_parser(
+        mut self,
+        parse: impl Fn(&str) -> Option<f32> + Send + Sync + 'static,
+    ) -> Self {
+        self.config = self.config.with_custom_parser(parse);
         self
     }
 
-    /// Sets the step size for value changes
+    /// Sets the label format and the matching parser at once
+    ///
+    /// # Example
+    /// ```no_run
+    /// use egui_knob::{Knob, KnobStyle, formatters};
+    /// # fn demo(ui: &mut egui_knob::egui::Ui) {
+    /// # let mut gain = 0.0;
+    /// ui.add(Knob::new(&mut gain, -24.0, 24.0, KnobStyle::Wiper).with_formatter(formatters::db()));
+    /// # }
+    /// ```
+    pub fn with_formatter(mut self, formatter: Formatter) -> Self {
+        self.config = self.config.with_formatter(formatter);
+        self
+    }
+
+    /// Parses text into a value using the configured parser
+    ///
+    /// Useful for custom text entry next to the knob. Returns `None` if the text is not
+    /// understood.
+    pub fn parse_value(&self, text: &str) -> Option<f32> {
+        self.config.parse_value(text)
+    }
+
+    /// Displays the value as a MIDI note name with octave, e.g. `60` as `"C4"`
+    ///
+    /// # Arguments
+    /// * `show_cents` - Appends the deviation from the nearest note, as in `"A4 +12¢"`
+    pub fn with_note_format(mut self, show_cents: bool) -> Self {
+        self.config = self.config.with_note_format(show_cents);
+        self
+    }
+
+    /// Displays the value as a duration, picking µs, ms or s automatically
+    ///
+    /// Suited for delay, attack or release knobs, e.g. `0.25` seconds shows as `"250 ms"`.
+    ///
+    /// # Arguments
+    /// * `unit` - Unit of the bound value
+    pub fn with_time_format(mut self, unit: TimeUnit) -> Self {
+        self.config = self.config.with_time_format(unit);
+        self
+    }
+
+    /// Appends a unit to the displayed value, e.g. `"Hz"` or `"dB"`
+    ///
+    /// Applies to labels and tooltips and works with any label format.
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.config = self.config.with_unit(unit);
+        self
+    }
+
+    /// Formats the value with automatic SI prefixes, e.g. `1500.0` as `"1.50 kHz"`
+    ///
+    /// Replaces the label format. The prefix is attached to the unit when one is set.
+    ///
+    /// # Arguments
+    /// * `significant_digits` - Number of significant digits to display
+    pub fn with_si_format(mut self, significant_digits: usize) -> Self {
+        self.config = self.config.with_si_format(significant_digits);
+        self
+    }
+
+    /// Sets the decimal and grouping separators of displayed and typed numbers
+    ///
+    /// Applies to the default and SI formats and to the default parser.
+    /// Custom label formats and parsers are left as they are.
+    pub fn with_number_locale(mut self, locale: NumberLocale) -> Self {
+        self.config = self.config.with_number_locale(locale);
+        self
+    }
+
+    /// Sets the step size for value changes as a fraction of the range
+    ///
+    /// The step also sets how far the knob turns per point of dragging.
+    #[deprecated(
+        since = "0.4.0",
+        note = "use `with_step_by` with a step in value units"
+    )]
+    #[allow(deprecated)]
     pub fn with_step(mut self, step: Option<f32>) -> Self {
-        self.config.step = step;
+        self.config = self.config.with_step(step);
+        self
+    }
+
+    /// Snaps the value to whole steps of `step`, counted from `min` in the knob's own units
+    ///
+    /// E.g. `1.0` on a `0.0..=100.0` knob gives whole numbers. Dragging keeps its
+    /// sensitivity and moves from step to step, while arrow keys and each scroll wheel
+    /// event move by one step. Values typed or pasted in snap too.
+    ///
+    /// # Arguments
+    /// * `step` - Distance between neighboring values, in the units of `min` and `max`.
+    ///   Must be finite and positive, other steps leave the value unstepped
+    pub fn with_step_by(mut self, step: f32) -> Self {
+        self.config = self.config.with_step_by(step);
+        self
+    }
+
+    /// Controls whether to draw the knob's outline circle
+    ///
+    /// Disable for arc-only or custom-painted designs.
+    pub fn with_outline(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_outline(enabled);
+        self
+    }
+
+    /// Draws evenly spaced tick marks along the sweep, near the edge of the knob
+    ///
+    /// Ticks are placed at both ends of the sweep, `0` disables them.
+    pub fn with_tick_marks(mut self, count: usize) -> Self {
+        self.config = self.config.with_tick_marks(count);
         self
     }
 
     /// Controls whether to show the background arc indicating the full range
     pub fn with_background_arc(mut self, enabled: bool) -> Self {
-        self.config.show_background_arc = enabled;
+        self.config = self.config.with_background_arc(enabled);
         self
     }
 
@@ -144,7 +570,112 @@ impl<'a> Knob<'a> {
     /// When enabled (and background arc is visible), displays a colored segment
     /// from the minimum position to the current value position.
     pub fn with_show_filled_segments(mut self, enabled: bool) -> Self {
-        self.config.show_filled_segments = enabled;
+        self.config = self.config.with_show_filled_segments(enabled);
+        self
+    }
+
+    /// Draws the filled segment from the center of the sweep instead of the minimum
+    ///
+    /// Suited for pan, balance or trim knobs, where the fill grows in either direction
+    /// from the middle position.
+    pub fn with_bipolar_fill(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_bipolar_fill(enabled);
+        self
+    }
+
+    /// Renders arcs as filled ring sectors instead of thick lines
+    ///
+    /// Recommended for thick strokes on small knobs, where polylines overlap themselves.
+    /// Ring sectors are not anti-aliased, so thin arcs look better without this.
+    pub fn with_mesh_arcs(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_mesh_arcs(enabled);
+        self
+    }
+
+    /// Sets how many straight segments approximate an arc over the full sweep
+    ///
+    /// Shorter arcs get a proportional share, at least one. Defaults to `128`; large
+    /// dashboards may get by with `16`, while a single large knob may want `256`.
+    pub fn with_arc_segments(mut self, segments: usize) -> Self {
+        self.config = self.config.with_arc_segments(segments);
+        self
+    }
+
+    /// Sets where the value arc is drawn relative to the knob body
+    ///
+    /// With [`ArcPlacement::Outside`] the arc becomes a ring around the knob and the
+    /// widget allocates the extra space it needs.
+    pub fn with_arc_placement(mut self, placement: ArcPlacement) -> Self {
+        self.config = self.config.with_arc_placement(placement);
+        self
+    }
+
+    /// Shows a peak marker that holds the highest value reached
+    ///
+    /// Useful for knobs that double as level displays. The marker is drawn as a short
+    /// tick on the arc.
+    ///
+    /// # Arguments
+    /// * `hold_time` - Seconds the peak is held before it starts to decay
+    /// * `decay_rate` - How fast the peak falls afterwards, as fraction of the full range per second,
+    ///   `0.0` to keep it until the value reaches it again
+    pub fn with_peak_hold(mut self, hold_time: f32, decay_rate: f32) -> Self {
+        self.config = self.config.with_peak_hold(hold_time, decay_rate);
+        self
+    }
+
+    /// Displays a live signal level as a thin ring inside the knob
+    ///
+    /// Call every frame with the current level, normalized to `0.0..=1.0`, e.g. to show
+    /// the post-gain level on a gain knob.
+    pub fn with_meter(mut self, level: f32) -> Self {
+        self.config = self.config.with_meter(level);
+        self
+    }
+
+    /// Turns the knob into a non-interactive gauge for rapidly changing external values
+    ///
+    /// The indicator follows the value smoothly instead of jumping, hover effects are
+    /// disabled and the bound value is never written.
+    ///
+    /// # Arguments
+    /// * `response_time` - Time constant of the needle movement in seconds, `0.0` disables smoothing
+    pub fn with_gauge_mode(mut self, response_time: f32) -> Self {
+        self.config = self.config.with_gauge_mode(response_time);
+        self
+    }
+
+    /// Shows the formatted value in a popup next to the pointer while dragging
+    ///
+    /// Keeps the value readable when the knob is covered by the cursor or finger, and
+    /// works without a static label.
+    pub fn with_drag_value_popup(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_drag_value_popup(enabled);
+        self
+    }
+
+    /// Shows the change relative to the value at the start of a drag, e.g. `+3.2 dB`
+    ///
+    /// The delta is formatted with the label format and an explicit sign.
+    pub fn with_drag_delta(mut self, readout: DeltaReadout) -> Self {
+        self.config = self.config.with_drag_delta(readout);
+        self
+    }
+
+    /// Shows a dimmed indicator at the position the value had when the drag started
+    pub fn with_drag_ghost(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_drag_ghost(enabled);
+        self
+    }
+
+    /// Briefly highlights the indicator when the value is changed from outside the widget
+    ///
+    /// Helps to spot which parameters moved after a preset load or during automation.
+    ///
+    /// # Arguments
+    /// * `duration` - Length of the highlight in seconds
+    pub fn with_change_pulse(mut self, duration: f32) -> Self {
+        self.config = self.config.with_change_pulse(duration);
         self
     }
 
@@ -152,99 +683,792 @@ impl<'a> Knob<'a> {
     ///
     /// Default is 0.005.
     pub fn with_drag_sensitivity(mut self, sensitivity: f32) -> Self {
-        self.config.drag_sensitivity = sensitivity;
+        self.config = self.config.with_drag_sensitivity(sensitivity);
+        self
+    }
+
+    /// Sets the default value of the knob
+    ///
+    /// A double-click, a Ctrl-click (Cmd-click on macOS) or the default action of assistive
+    /// technology resets the knob to it. A tick on the arc marks it, see
+    /// [`Knob::with_default_marker`], and it's where [`Knob::with_spring_return`] returns to.
+    pub fn with_default(mut self, value: f32) -> Self {
+        self.config = self.config.with_default(value);
         self
     }
 
     /// Sets a reset value to return to on doubleclick event.
+    ///
+    /// Same as [`Knob::with_default`].
     pub fn with_double_click_reset(mut self, reset_value: f32) -> Self {
-        self.config.reset_value = Some(reset_value);
+        self.config = self.config.with_double_click_reset(reset_value);
+        self
+    }
+
+    /// Controls whether a tick marks the default value on the arc
+    ///
+    /// Default is `true`, it's only drawn once a default is set.
+    pub fn with_default_marker(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_default_marker(enabled);
+        self
+    }
+
+    /// Returns the knob to its default value when a drag is released, like a pitch wheel
+    ///
+    /// Needs a default value, see [`Knob::with_default`].
+    pub fn with_spring_return(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_spring_return(enabled);
         self
     }
 
     /// Allows user to use scroll wheel to change knob value
-    /// Uses config.step for the increment value
+    /// Scrolls by whole steps when a step is set with [`with_step_by`](Self::with_step_by)
     pub fn with_middle_scroll(mut self) -> Self {
-        self.config.allow_scroll = true;
+        self.config = self.config.with_middle_scroll();
+        self
+    }
+
+    /// Reports edits as begin/perform/end gestures, as plugin hosts expect for automation
+    ///
+    /// The handler is borrowed for this frame only, so it can wrap e.g. the host's
+    /// parameter setter.
+    pub fn with_gesture_handler(mut self, handler: impl ParamGestureHandler + 'a) -> Self {
+        self.gestures = Some(Box::new(handler));
+        self
+    }
+
+    /// Calls `on_learn` when the user asks the knob to learn a controller mapping
+    ///
+    /// Use it to start MIDI learn for the parameter, and show the pending state with
+    /// [`Knob::with_learn_armed`] until a controller was moved.
+    ///
+    /// # Arguments
+    /// * `gesture` - How the user starts learning
+    /// * `on_learn` - Called once per request
+    pub fn with_learn(mut self, gesture: InvokeGesture, on_learn: impl FnMut() + 'a) -> Self {
+        self.learn = Some((gesture, Box::new(on_learn)));
+        self
+    }
+
+    /// Copies the value with Ctrl+C and pastes a typed value with Ctrl+V
+    ///
+    /// Works while the knob is hovered or focused, and adds "Copy" and "Paste" entries to
+    /// its context menu. Pasted text is read with the knob's parser, see
+    /// [`Knob::with_custom_parser`].
+    pub fn with_clipboard(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_clipboard(enabled);
+        self
+    }
+
+    /// Shows the host's automated value, and follows it while the knob isn't touched
+    ///
+    /// Call every frame with the value of the automation lane, or `None` without one. A
+    /// dot marks the automated value while the user's value overrides it during a touch.
+    /// Pair it with [`Knob::with_touch`] to tell the host when the override starts and ends.
+    pub fn with_automation(mut self, automated: Option<f32>) -> Self {
+        self.config = self.config.with_automation(automated);
+        self
+    }
+
+    /// Calls `on_touch` when the pointer is pressed on the knob and `on_release` when it's
+    /// released again
+    ///
+    /// Unlike a drag, a touch starts with the press, before the pointer moves, as DAWs
+    /// expect for automation touch mode.
+    pub fn with_touch(
+        mut self,
+        on_touch: impl FnMut() + 'a,
+        on_release: impl FnMut() + 'a,
+    ) -> Self {
+        self.touch = Some((Box::new(on_touch), Box::new(on_release)));
+        self
+    }
+
+    /// Glides the value to `target` over `duration` seconds, starting this frame
+    ///
+    /// Call it once, e.g. when recalling a preset or snapping back to a default. The knob
+    /// keeps moving in the following frames on its own, and stops when the user changes
+    /// the value. The steps mark the response changed with [`ChangeCause::Animation`]. The
+    /// whole glide is one gesture and one undo edit, also when it is retargeted on the way.
+    pub fn animate_to(mut self, target: f32, duration: f32) -> Self {
+        self.animation = Some((target, duration));
+        self
+    }
+
+    /// Adds a pop-up editor to type in an exact value
+    ///
+    /// The editor shows the unit and the range next to the text field. Enter applies the
+    /// value, Escape or clicking elsewhere closes it without a change. The text is read with
+    /// the knob's parser, see [`Knob::with_custom_parser`].
+    ///
+    /// # Arguments
+    /// * `gesture` - How the user opens the editor
+    pub fn with_value_editor(mut self, gesture: InvokeGesture) -> Self {
+        self.config = self.config.with_value_editor(gesture);
+        self
+    }
+
+    /// Calls `on_edit` once per completed gesture, e.g. to push an undo entry
+    ///
+    /// Unlike [`Response::changed`], which fires on every frame of a drag, this reports a
+    /// whole drag, scroll burst, reset or typed value as one [`ValueEdit`].
+    pub fn with_undo_hook(mut self, on_edit: impl FnMut(ValueEdit) + 'a) -> Self {
+        self.undo = Some(Box::new(on_edit));
+        self
+    }
+
+    /// Highlights the outline while the knob waits for a controller to be mapped
+    pub fn with_learn_armed(mut self, armed: bool) -> Self {
+        self.config = self.config.with_learn_armed(armed);
+        self
+    }
+
+    /// Sets a stable id for the knob, instead of one derived from its position in the `Ui`
+    ///
+    /// Needed when knobs are created in loops or change order, so drag and hover state
+    /// stays with the right knob. The id is combined with the id of the parent `Ui`.
+    pub fn with_id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.config = self.config.with_id_salt(id_salt);
+        self
+    }
+
+    /// Sets the value put in place of a bound value that is NaN
+    ///
+    /// The knob writes the fallback to the bound value before drawing. Range knobs put a
+    /// NaN end at their own end of the range instead. Without a finite `min` and `max` the
+    /// knob can't map input to values, so it's drawn at the start of its sweep and ignores
+    /// input.
+    pub fn with_nan_fallback(mut self, fallback: NanFallback) -> Self {
+        self.config = self.config.with_nan_fallback(fallback);
+        self
+    }
+
+    /// Sets what the knob does with a bound value outside of `min..=max`
+    ///
+    /// By default the value is left alone until the user changes it, while the indicator
+    /// rests at the nearest end. [`KnobResponse::clamped`] reports when it was clamped.
+    pub fn with_out_of_range(mut self, policy: OutOfRange) -> Self {
+        self.config = self.config.with_out_of_range(policy);
+        self
+    }
+
+    /// Binds the knob to a normalized value in `0.0..=1.0` instead of one in `min..=max`
+    ///
+    /// For hosts that store parameters normalized, like most plugin APIs. The knob still
+    /// displays, formats and parses values in `min..=max`, and the default, automation,
+    /// [`animate_to`](Self::animate_to) target and callbacks stay in those units too.
+    /// Logarithmic scaling applies to the mapping.
+    pub fn with_normalized_value(mut self, enabled: bool) -> Self {
+        self.config = self.config.with_normalized_value(enabled);
         self
     }
+
+    /// Spreads the range over the sweep logarithmically, e.g. for frequencies
+    ///
+    /// The range is mapped onto one decade, so the lower part of the range gets more of
+    /// the sweep.
     pub fn with_logarithmic_scaling(mut self) -> Self {
-        self.config.logarithmic_scaling = true;
+        self.config = self.config.with_logarithmic_scaling();
         self
     }
 }
 
 impl Widget for Knob<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
-        if self.value.is_nan() {
-            *self.value = self.min;
+        self.show(ui).response
+    }
+}
+
+impl Knob<'_> {
+    /// Shows the knob in `rect` without allocating space in the layout
+    ///
+    /// Use this to place knobs absolutely, e.g. over background artwork in a skinned GUI.
+    /// The knob and its labels are centered in `rect`, which responds to input as a whole.
+    /// Under [`Ui::put`] the knob behaves the same, centered in the given rect.
+    pub fn paint_at(self, ui: &mut Ui, rect: Rect) -> KnobResponse {
+        self.show_in(ui, Some(rect))
+    }
+
+    /// Shows the knob, like `ui.add(knob)`, returning the values along with the response
+    pub fn show(self, ui: &mut Ui) -> KnobResponse {
+        self.show_in(ui, None)
+    }
+
+    /// Copies the formatted value to the clipboard and keeps the number for pasting
+    fn copy_value(&self, ui: &Ui) {
+        ui.ctx().copy_text(self.config.format_value(*self.value));
+        ui.data_mut(|d| d.insert_temp(Id::new(CLIPBOARD_ID), *self.value));
+    }
+
+    /// Sets a pasted value, snapped to the step and clamped to the range
+    fn paste_value(&mut self, value: f32) {
+        if !value.is_nan() {
+            let value = math::clamp_to_range(value, self.min, self.max);
+            *self.value = self.config.snap_value(value, self.min, self.max);
+        }
+    }
+
+    fn show_in(mut self, ui: &mut Ui, target: Option<Rect>) -> KnobResponse {
+        let available_width = target.map_or_else(|| ui.available_width(), |rect| rect.width());
+        self.config.prepare(ui, available_width, self.min, self.max);
+
+        // A normalized binding is worked on as a plain value and written back at the end
+        let id = self.config.widget_id(ui, ui.next_auto_id());
+        let binding = self.config.normalized_value.then(|| {
+            let normalized = *self.value;
+            let plain = NormalizedBinding::plain(ui, id, normalized, |raw| {
+                self.config.denormalize(raw, self.min, self.max)
+            });
+            *self.value = plain;
+            (normalized, plain)
+        });
+
+        *self.value = self.config.sanitize(*self.value, self.min, self.max);
+        // An empty or non-finite range has no values to turn through, so the knob is parked
+        let inert = self.min == self.max || !(self.min.is_finite() && self.max.is_finite());
+        let interactive = self.config.gauge_response_time.is_none() && !inert;
+
+        // Automation drives the value until the user touches the knob
+        let automated = if let Some(automated) = self.config.automation
+            && !automated.is_nan()
+            && !KnobState::load(ui.ctx(), id).is_some_and(|state| state.touched)
+        {
+            *self.value = math::clamp_to_range(automated, self.min, self.max);
+            true
+        } else {
+            false
+        };
+
+        let (lowest, highest) = (self.min.min(self.max), self.min.max(self.max));
+        let out_of_range = !(lowest..=highest).contains(&*self.value);
+        if out_of_range && self.config.out_of_range == OutOfRange::Clamp {
+            *self.value = math::clamp_to_range(*self.value, lowest, highest);
+        }
+
+        let incoming = *self.value;
+
+        // A running animation moves the value before the frame's interaction, so the user
+        // can grab the knob at any time. Automation takes precedence over it.
+        let mut animation_begins = false;
+        if let Some((target, duration)) = self.animation {
+            let target = math::clamp_to_range(target, self.min, self.max);
+            let from = (
+                *self.value,
+                self.config.normalize(*self.value, self.min, self.max),
+            );
+            let to = (target, self.config.normalize(target, self.min, self.max));
+            animation_begins = ValueAnimation::start(ui, id, from, to, duration);
         }
+        let animation = ValueAnimation::advance(ui, id, |raw| {
+            self.config.denormalize(raw, self.min, self.max)
+        })
+        .filter(|_| !automated);
+        let animation_begins = animation_begins && animation.is_some();
+        if let Some(step) = animation {
+            *self.value = step.value;
+        }
+        let before_input = *self.value;
 
-        let mut raw = if self.config.logarithmic_scaling {
-            remap(*self.value, self.min..=self.max, 1.0..=10.0).log(10.0)
+        let mut raw = if inert {
+            0.0
         } else {
-            remap(*self.value, self.min..=self.max, 0.0..=1.0)
+            self.config.normalize(*self.value, self.min, self.max)
         };
 
         let renderer = KnobRenderer::new(&self.config, *self.value, raw, self.min, self.max);
-        let adjusted_size = renderer.calculate_size(ui);
+        let layout = renderer.calculate_layout(ui.painter());
+        let adjusted_size = layout.size();
 
-        let (rect, response) = ui.allocate_exact_size(adjusted_size, Sense::click_and_drag());
+        let sense = if !interactive {
+            Sense::hover()
+        } else {
+            Sense::click_and_drag()
+        };
+        // Justified layouts hand out more space than asked for. The whole space responds
+        // to input, while knob and labels are aligned in it as the layout asks.
+        let (rect, response) = match target {
+            Some(rect) => {
+                let id = self.config.widget_id(ui, ui.next_auto_id());
+                ui.skip_ahead_auto_ids(1);
+                let response = ui.interact(rect, id, sense);
+                (
+                    Align2::CENTER_CENTER.align_size_within_rect(adjusted_size, rect),
+                    response,
+                )
+            }
+            None => {
+                let (auto_id, rect) = ui.allocate_space(adjusted_size);
+                let id = self.config.widget_id(ui, auto_id);
+                let response = ui.interact(rect, id, sense);
+                (
+                    ui.layout().align_size_within_rect(adjusted_size, rect),
+                    response,
+                )
+            }
+        };
 
         let mut response = response;
-        if response.dragged() {
-            let delta = response.drag_delta().y;
-            let step = self.config.step.unwrap_or(self.config.drag_sensitivity);
-            raw = (raw - delta * step).clamp(0.0,1.0);
-
-            raw = if let Some(step) = self.config.step {
-                let steps = (raw / step).round();
-                (steps * step).clamp(0.0, 1.0)
-            } else {
-                raw
-            };
+        let mut state = KnobState::load(ui.ctx(), response.id).unwrap_or_default();
+        let touched = response.is_pointer_button_down_on();
+        if touched != state.touched
+            && let Some((on_touch, on_release)) = &mut self.touch
+        {
+            if touched { on_touch() } else { on_release() }
+        }
+        state.touched = touched;
+        let previous_drag = state.drag;
+        let drag = state.track_drag(&response, *self.value, raw);
+        let mut scrolled = false;
+        let mut cause = animation.map(|_| ChangeCause::Animation);
+        if let Some(response_time) = self.config.gauge_response_time {
+            raw = GaugeState::smooth(ui, response.id, raw, response_time, |raw| {
+                self.config.denormalize(raw, self.min, self.max)
+            });
+        } else if interactive {
+            // Interaction starts where the indicator is drawn, also for values out of range
+            raw = raw.clamp(0.0, 1.0);
+            let raw_before = raw;
+            if response.dragged() {
+                let delta = response.drag_delta().y;
+                let speed = self.config.drag_speed();
+                raw = (raw + state.step_remainder - delta * speed).clamp(0.0, 1.0);
 
-            if self.value.is_nan() {
-                *self.value = 0.0;
+                if self.config.is_stepped() {
+                    let snapped = self.config.snap_raw(raw, self.min, self.max);
+                    // Keep what was rounded away, so slow drags still reach the next step
+                    state.step_remainder = raw - snapped;
+                    raw = snapped;
+                }
+            }  else if response.hovered() & self.config.allow_scroll && let Some(scoll) = ui.input(|input| {
+                    input.events.iter().find_map(|e| match e {
+                        // Horizontal-only scrolling doesn't turn the knob
+                        egui::Event::MouseWheel { delta, .. } if delta.y != 0.0 => Some(*delta),
+                        _ => None,
+                    })
+                }) {
+                raw = if self.config.value_step.is_some() {
+                    // One step per wheel event, however far the wheel turned
+                    let steps = scoll.y.signum();
+                    self.config.step_raw(raw, steps, self.min, self.max, 0.0)
+                } else {
+                    (raw + scoll.y * self.config.drag_speed()).clamp(0.0, 1.0)
+                };
             }
 
-            response.mark_changed();
-        }  else if response.hovered() & self.config.allow_scroll && let Some(scoll) = ui.input(|input| {
-                input.events.iter().find_map(|e| match e {
-                    egui::Event::MouseWheel { delta, .. } => Some(*delta),
+            scrolled = !response.dragged() && raw != raw_before;
+
+            let focused = response.has_focus();
+            if focused {
+                // Arrow keys turn the knob instead of moving the focus
+                ui.memory_mut(|m| {
+                    m.set_focus_lock_filter(
+                        response.id,
+                        EventFilter {
+                            horizontal_arrows: true,
+                            vertical_arrows: true,
+                            ..Default::default()
+                        },
+                    );
+                });
+            }
+
+            let (increments, set_value) = ui.input(|input| {
+                let count = |action| input.num_accesskit_action_requests(response.id, action);
+                let set_value = input
+                    .accesskit_action_requests(response.id, Action::SetValue)
+                    .filter_map(|request| match request.data {
+                        Some(ActionData::NumericValue(value)) => Some(value as f32),
+                        _ => None,
+                    })
+                    .last();
+                let mut increments =
+                    count(Action::Increment) as f32 - count(Action::Decrement) as f32;
+                if focused {
+                    let presses = |keys: [Key; 2]| {
+                        keys.map(|key| input.num_presses(key)).iter().sum::<usize>() as f32
+                    };
+                    increments += presses([Key::ArrowUp, Key::ArrowRight])
+                        - presses([Key::ArrowDown, Key::ArrowLeft]);
+                }
+                (increments, set_value)
+            });
+            if increments != 0.0 {
+                raw = self
+                    .config
+                    .step_raw(raw, increments, self.min, self.max, ACCESSIBLE_STEP);
+            }
+
+            // Round-tripping through the normalized position perturbs the value slightly,
+            // so it's only written back when an interaction actually moved the knob.
+            if raw != raw_before {
+                let value = self.config.denormalize(raw, self.min, self.max);
+                *self.value = self.config.snap_value(value, self.min, self.max);
+                cause = Some(if response.dragged() {
+                    ChangeCause::Drag
+                } else if scrolled {
+                    ChangeCause::Scroll
+                } else {
+                    ChangeCause::Keyboard
+                });
+            }
+
+            let reset = response.double_clicked()
+                || (response.clicked()
+                    && ui.input(|i| i.modifiers.matches_exact(Modifiers::COMMAND)))
+                || ui.input(|i| i.has_accesskit_action_request(response.id, Action::Click))
+                || (self.config.spring_return && response.drag_stopped());
+            if reset && let Some(default_value) = self.config.default_value {
+                *self.value = math::clamp_to_range(default_value, self.min, self.max);
+                cause = Some(ChangeCause::Reset);
+            }
+
+            if let Some(value) = set_value {
+                self.paste_value(value);
+                cause = Some(ChangeCause::TextEntry);
+            }
+        }
+
+        if self.config.clipboard
+            && (response.has_focus()
+                || (response.hovered() && ui.memory(|m| m.focused().is_none())))
+        {
+            let (copy, paste) = ui.input(|input| {
+                let copy = input.events.iter().any(|e| matches!(e, Event::Copy));
+                let paste = input.events.iter().rev().find_map(|e| match e {
+                    Event::Paste(text) => Some(text.clone()),
                     _ => None,
-                })
-            }) {
-            raw = (raw
-                + scoll.y * self.config.step.unwrap_or(self.config.drag_sensitivity))
-            .clamp(0.0, 1.0);
+                });
+                (copy, paste)
+            });
+            if copy {
+                self.copy_value(ui);
+            }
+            if interactive
+                && let Some(value) = paste.and_then(|text| self.config.parse_value(&text))
+            {
+                self.paste_value(value);
+                cause = Some(ChangeCause::TextEntry);
+            }
+        }
+
+        let mut open_editor = false;
+        let editor_in_menu =
+            interactive && self.config.value_editor == Some(InvokeGesture::ContextMenu);
+        let learn_in_menu = matches!(self.learn, Some((InvokeGesture::ContextMenu, _)));
+        if self.config.clipboard || editor_in_menu || learn_in_menu {
+            let copied = ui.data(|d| d.get_temp::<f32>(Id::new(CLIPBOARD_ID)));
+            response.context_menu(|ui| {
+                if editor_in_menu && ui.button("Edit Value…").clicked() {
+                    open_editor = true;
+                    ui.close();
+                }
+                if self.config.clipboard {
+                    if ui.button("Copy").clicked() {
+                        self.copy_value(ui);
+                        ui.close();
+                    }
+                    let paste =
+                        ui.add_enabled(interactive && copied.is_some(), Button::new("Paste"));
+                    if paste.clicked()
+                        && let Some(value) = copied
+                    {
+                        self.paste_value(value);
+                        cause = Some(ChangeCause::TextEntry);
+                        ui.close();
+                    }
+                }
+                if let Some((InvokeGesture::ContextMenu, on_learn)) = &mut self.learn {
+                    if self.config.clipboard || editor_in_menu {
+                        ui.separator();
+                    }
+                    if ui.button("MIDI Learn").clicked() {
+                        on_learn();
+                        ui.close();
+                    }
+                }
+            });
+        }
+        if let Some((InvokeGesture::Click(modifiers), on_learn)) = &mut self.learn
+            && response.clicked()
+            && ui.input(|i| i.modifiers.matches_exact(*modifiers))
+        {
+            on_learn();
         }
 
-        *self.value = if self.config.logarithmic_scaling {
-            remap(10f32.powf(raw), 1.0..=10.0, self.min..=self.max)
-        }else {
-            remap(raw, 0.0..=1.0, self.min..=self.max)
+        if let Some(InvokeGesture::Click(modifiers)) = self.config.value_editor
+            && interactive
+            && response.clicked()
+            && ui.input(|i| i.modifiers.matches_exact(modifiers))
+        {
+            open_editor = true;
+        }
+        if open_editor {
+            state.edit_text = Some(self.config.format_editable(*self.value));
+        }
+        if let Some(text) = &mut state.edit_text {
+            let outcome = editor::show(
+                ui,
+                response.id,
+                rect,
+                text,
+                open_editor,
+                &self.config,
+                (self.min, self.max),
+            );
+            match outcome {
+                EditorOutcome::Open => {}
+                EditorOutcome::Commit(value) => {
+                    self.paste_value(value);
+                    cause = Some(ChangeCause::TextEntry);
+                    state.edit_text = None;
+                }
+                EditorOutcome::Cancel => state.edit_text = None,
+            }
+        }
+
+        // Drawing and accessibility follow the final value through the same taper as the
+        // interaction, also when it was reset, pasted or typed in this frame
+        if interactive && *self.value != incoming {
+            raw = self.config.normalize(*self.value, self.min, self.max);
+        }
+
+        if *self.value != incoming {
+            response.mark_changed();
+        } else {
+            cause = None;
+        }
+        let animated = cause == Some(ChangeCause::Animation);
+        // The user taking over ends the animation
+        let animation_ends = match animation {
+            Some(step) if step.finished => true,
+            Some(_) if response.drag_started() || (cause.is_some() && !animated) => {
+                ValueAnimation::cancel(ui, response.id);
+                true
+            }
+            _ => false,
         };
+        let changed_elsewhere =
+            LastValue::changed_elsewhere(ui, response.id, incoming, *self.value);
+        if changed_elsewhere && cause.is_none() {
+            cause = Some(ChangeCause::External);
+        }
+        if let Some(gestures) = &mut self.gestures {
+            let normalized = self.config.normalize(*self.value, self.min, self.max);
+            // An animation is one gesture from start to end, ended before any user edit
+            if animation_begins {
+                gestures.begin_edit();
+            }
+            if animated {
+                gestures.perform_edit(*self.value, normalized);
+            }
+            if animation_ends {
+                gestures.end_edit();
+            }
+            // A change when the drag is released still belongs to the drag's gesture
+            let edited = response.changed() && !animated;
+            let discrete = !response.dragged() && !response.drag_stopped();
+            if response.drag_started() || (discrete && edited) {
+                gestures.begin_edit();
+            }
+            if edited {
+                gestures.perform_edit(*self.value, normalized);
+            }
+            if response.drag_stopped() || (discrete && edited) {
+                gestures.end_edit();
+            }
+        }
+        if let Some(on_edit) = &mut self.undo {
+            let id = response.id;
+            let mut commit = |old_value: f32, new_value: f32| {
+                if old_value != new_value {
+                    on_edit(ValueEdit {
+                        id,
+                        old_value,
+                        new_value,
+                    });
+                }
+            };
 
-        if response.double_clicked()
-            && let Some(reset_value) = self.config.reset_value {
-                *self.value = reset_value
+            if animation_ends && let Some(step) = animation {
+                commit(step.start_value, before_input);
             }
 
-        let knob_rect = renderer.calculate_knob_rect(rect);
+            let now = ui.input(|i| i.time);
+            if scrolled {
+                let start_value = state
+                    .scroll_burst
+                    .map_or(before_input, |burst| burst.start_value);
+                state.scroll_burst = Some(ScrollBurst {
+                    start_value,
+                    last_time: now,
+                });
+                ui.ctx().request_repaint_after_secs(SCROLL_BURST_GAP as f32);
+            } else if let Some(burst) = state.scroll_burst {
+                let idle = now - burst.last_time;
+                if response.changed() || response.drag_started() || idle >= SCROLL_BURST_GAP {
+                    commit(burst.start_value, before_input);
+                    state.scroll_burst = None;
+                } else {
+                    ui.ctx()
+                        .request_repaint_after_secs((SCROLL_BURST_GAP - idle) as f32);
+                }
+            }
+
+            if response.drag_stopped()
+                && let Some(drag) = previous_drag
+            {
+                commit(drag.start_value, *self.value);
+            } else if response.changed() && !animated && !scrolled && !response.dragged() {
+                commit(before_input, *self.value);
+            }
+        }
+        state.store(ui.ctx(), response.id);
+
+        let label = self.config.label.as_deref().unwrap_or_default();
+        let enabled = ui.is_enabled() && !inert;
+        response.widget_info(|| WidgetInfo::slider(enabled, *self.value as f64, label));
+        ui.ctx().accesskit_node_builder(response.id, |builder| {
+            builder.set_min_numeric_value(self.min.min(self.max) as f64);
+            builder.set_max_numeric_value(self.min.max(self.max) as f64);
+            if !self.config.logarithmic_scaling {
+                let step = self.config.step_fraction(self.min, self.max);
+                let step = step.unwrap_or(ACCESSIBLE_STEP) * (self.max - self.min);
+                builder.set_numeric_value_step(step.abs() as f64);
+            }
+            builder.set_value(self.config.format_value(*self.value));
+            if interactive {
+                builder.add_action(Action::SetValue);
+                if raw < 1.0 {
+                    builder.add_action(Action::Increment);
+                }
+                if raw > 0.0 {
+                    builder.add_action(Action::Decrement);
+                }
+            }
+        });
+
+        let knob_rect = layout.knob_rect(rect, self.config.knob_align);
         let center = knob_rect.center();
-        let radius = self.config.size / 2.0;
+        let radius = self.config.size / 2.0 * self.config.visual_scale;
+
+        let peak = self.config.peak_hold.map(|peak_hold| {
+            PeakState::update(
+                ui,
+                response.id,
+                raw,
+                peak_hold.hold_time,
+                peak_hold.decay_rate,
+            )
+        });
+
+        let pulse = self.config.pulse_duration.map_or(0.0, |duration| {
+            PulseState::update(ui, response.id, changed_elsewhere, duration)
+        });
+
+        let drag_delta = drag.map(|drag| *self.value - drag.start_value);
+        let ghost = drag
+            .filter(|_| self.config.show_drag_ghost)
+            .map(|drag| drag.start_raw);
+
+        let exceeded = if self.config.out_of_range == OutOfRange::Indicate {
+            match self.config.normalize(*self.value, self.min, self.max) {
+                raw if raw < 0.0 => Some(0.0),
+                raw if raw > 1.0 => Some(1.0),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        let updated_renderer =
+            KnobRenderer::new(&self.config, *self.value, raw, self.min, self.max)
+                .with_exceeded(exceeded)
+                .with_peak(peak)
+                .with_drag_delta(drag_delta)
+                .with_ghost(ghost)
+                .with_pulse(pulse);
+        let hovered = response.hovered() && interactive;
+        let mut painter = ui.painter().clone();
+        if inert {
+            painter.multiply_opacity(ui.visuals().disabled_alpha());
+        }
+        updated_renderer.render_knob(&painter, center, radius, hovered);
+        KnobGeometry {
+            center,
+            radius,
+            angle: updated_renderer.compute_angle(),
+        }
+        .store(ui, response.id);
+        let label_truncated = updated_renderer.render_label(&painter, rect, knob_rect);
+
+        let popup_delta =
+            drag_delta.filter(|_| self.config.delta_readout == Some(DeltaReadout::Popup));
+        let drag_value = self.config.drag_value_popup
+            || matches!(self.config.tooltip, TooltipMode::WhileDragging);
+        if response.dragged() && (drag_value || popup_delta.is_some()) {
+            egui::Tooltip::for_widget(&response)
+                .at_pointer()
+                .gap(12.0)
+                .show(|ui| {
+                    if drag_value {
+                        ui.label(self.config.format_value(*self.value));
+                    }
+                    if let Some(delta) = popup_delta {
+                        ui.label(self.config.format_delta(delta));
+                    }
+                });
+        } else if hovered {
+            let show_full_label = self.config.tooltip_only_labels
+                || (label_truncated && !matches!(self.config.tooltip, TooltipMode::Off));
 
-        let updated_renderer = KnobRenderer::new(&self.config, *self.value, raw, self.min, self.max);
-        updated_renderer.render_knob(ui.painter(), center, radius, response.hovered());
-        updated_renderer.render_label(ui, rect);
+            if show_full_label {
+                let value_text = self.config.format_value(*self.value);
+                let text = match &self.config.label {
+                    Some(label) => format!("{}: {}", label, value_text),
+                    None => value_text,
+                };
+                response.clone().on_hover_text(text);
+            } else {
+                match &self.config.tooltip {
+                    TooltipMode::Off | TooltipMode::WhileDragging => {}
+                    TooltipMode::Value => {
+                        if self.config.label.is_some() {
+                            response
+                                .clone()
+                                .on_hover_text(self.config.format_value(*self.value));
+                        }
+                    }
+                    TooltipMode::AlwaysValue => {
+                        response
+                            .clone()
+                            .on_hover_text(self.config.format_value(*self.value));
+                    }
+                    TooltipMode::Custom(content) => {
+                        response.clone().on_hover_ui(|ui| content(ui, *self.value));
+                    }
+                }
+            }
+        }
 
-        if self.config.label.is_some() && response.hovered() {
-            response
-                .clone()
-                .on_hover_text((self.config.label_format)(*self.value));
+        let value = *self.value;
+        let normalized = if inert {
+            0.0
+        } else {
+            self.config.normalize(value, self.min, self.max)
+        };
+        if let Some((bound, plain)) = binding {
+            let written = if value == plain { bound } else { normalized };
+            NormalizedBinding::store(ui, response.id, written, value);
+            *self.value = written;
         }
 
-        response
+        KnobResponse {
+            normalized,
+            response,
+            old_value: incoming,
+            value,
+            cause,
+            clamped: out_of_range && (lowest..=highest).contains(&value),
+            inert,
+        }
     }
 }
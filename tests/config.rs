@@ -0,0 +1,56 @@
+use egui_knob::egui::Color32;
+use egui_knob::{KnobConfig, KnobPreset, KnobStyle, KnobTheme, TooltipMode, formatters};
+
+#[test]
+fn config_debug_hides_closures() {
+    let config = KnobConfig::new(KnobStyle::Dot)
+        .with_unit("dB")
+        .with_formatter(formatters::db())
+        .with_tooltip(TooltipMode::Custom(std::sync::Arc::new(|_, _| {})));
+    let text = format!("{config:?}");
+    assert!(text.starts_with("KnobConfig {"), "{text}");
+    assert!(text.contains("style: Dot"), "{text}");
+    assert!(text.contains("unit: Some(\"dB\")"), "{text}");
+    assert!(text.contains("label_format: Some(<fn>)"), "{text}");
+    assert!(text.contains("tooltip: Custom(..)"), "{text}");
+}
+
+#[test]
+fn cloned_config_is_independent() {
+    let config = KnobConfig::new(KnobStyle::Wiper).with_size(40.0);
+    let variant = config.clone().with_size(20.0);
+    assert!(format!("{config:?}").contains("size: 40.0"));
+    assert!(format!("{variant:?}").contains("size: 20.0"));
+    assert!(format!("{:?}", KnobPreset::new(variant)).contains("size: 20.0"));
+}
+
+#[test]
+fn config_can_be_logarithmic() {
+    let config = KnobConfig::new(KnobStyle::Wiper).with_logarithmic_scaling();
+    assert!(format!("{config:?}").contains("logarithmic_scaling: true"));
+}
+
+/// WCAG contrast ratio between two opaque colors
+fn contrast(a: Color32, b: Color32) -> f32 {
+    let luminance = |color: Color32| {
+        let [r, g, b, _] = egui_knob::egui::Rgba::from(color).to_array();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    };
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+#[test]
+fn high_contrast_theme_meets_wcag_ratios() {
+    let theme = KnobTheme::high_contrast();
+    let colors = &theme.colors;
+    for color in [
+        colors.text_color,
+        colors.value_text_color,
+        colors.line_color,
+    ] {
+        assert!(contrast(color, colors.cap_fill_color) >= 7.0);
+    }
+    assert!(contrast(colors.arc_background_color, colors.cap_fill_color) >= 3.0);
+    assert!(contrast(colors.arc_fill_color, colors.arc_background_color) >= 3.0);
+}
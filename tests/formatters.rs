@@ -0,0 +1,83 @@
+use egui_knob::formatters::{self, Formatter};
+
+fn assert_round_trip(formatter: &Formatter, value: f32) {
+    let text = formatter.format(value);
+    let parsed = formatter
+        .parse(&text)
+        .unwrap_or_else(|| panic!("could not parse {text:?}"));
+    assert!(
+        (parsed - value).abs() <= value.abs() * 1e-2 + 1e-3 || parsed == value,
+        "{value} was formatted as {text:?} and parsed as {parsed}"
+    );
+}
+
+#[test]
+fn db() {
+    let db = formatters::db();
+    assert_eq!(db.format(-6.0), "-6.0 dB");
+    assert_eq!(db.format(f32::NEG_INFINITY), "-inf dB");
+    assert_eq!(db.parse("-3dB"), Some(-3.0));
+    assert_eq!(db.parse("+1.5 db"), Some(1.5));
+    for value in [-60.0, -6.0, 0.0, 12.0, f32::NEG_INFINITY] {
+        assert_round_trip(&db, value);
+    }
+}
+
+#[test]
+fn hz() {
+    let hz = formatters::hz();
+    assert_eq!(hz.format(440.0), "440 Hz");
+    assert_eq!(hz.format(1500.0), "1.50 kHz");
+    assert_eq!(hz.parse("2k"), Some(2000.0));
+    assert_eq!(hz.parse("1.5 kHz"), Some(1500.0));
+    for value in [20.0, 440.0, 12_345.0] {
+        assert_round_trip(&hz, value);
+    }
+}
+
+#[test]
+fn percent() {
+    let percent = formatters::percent();
+    assert_eq!(percent.format(0.5), "50%");
+    assert_eq!(percent.parse("25 %"), Some(0.25));
+    assert_eq!(percent.parse("75"), Some(0.75));
+    for value in [0.0, 0.33, 1.0] {
+        assert_round_trip(&percent, value);
+    }
+}
+
+#[test]
+fn semitones() {
+    let semitones = formatters::semitones();
+    assert_eq!(semitones.format(7.0), "+7 st");
+    assert_eq!(semitones.format(-0.5), "-0.5 st");
+    assert_eq!(semitones.format(-0.001), "+0 st");
+    assert_eq!(semitones.parse("-12"), Some(-12.0));
+    for value in [-24.0, 0.0, 3.25] {
+        assert_round_trip(&semitones, value);
+    }
+}
+
+#[test]
+fn ms() {
+    let ms = formatters::ms();
+    assert_eq!(ms.format(250.0), "250 ms");
+    assert_eq!(ms.format(1200.0), "1.20 s");
+    assert_eq!(ms.parse("250"), Some(250.0));
+    assert_eq!(ms.parse("1.2 s"), Some(1200.0));
+    assert!((ms.parse("800 µs").unwrap() - 0.8).abs() < 1e-6);
+    for value in [0.5, 15.0, 250.0, 2500.0] {
+        assert_round_trip(&ms, value);
+    }
+}
+
+#[test]
+fn ratio() {
+    let ratio = formatters::ratio();
+    assert_eq!(ratio.format(4.0), "4.0:1");
+    assert_eq!(ratio.format(f32::INFINITY), "∞:1");
+    assert_eq!(ratio.parse("8"), Some(8.0));
+    for value in [1.0, 2.5, 20.0, f32::INFINITY] {
+        assert_round_trip(&ratio, value);
+    }
+}
@@ -0,0 +1,1230 @@
+use std::ops::RangeInclusive;
+
+use egui::accesskit::Role;
+use egui::{Event, Modifiers, MouseWheelUnit, TouchPhase, Vec2, vec2};
+use egui_kittest::Harness;
+use egui_kittest::kittest::{NodeT, Queryable};
+use egui_knob::{
+    ChangeCause, InvokeGesture, JogWheel, Knob, KnobConfig, KnobGeometry, KnobResponse, KnobState,
+    KnobStyle, LabelPosition, NanFallback, OutOfRange, ParamGestureHandler, RotarySwitch,
+    StereoKnobs, TooltipMode, ValueEdit, egui,
+};
+
+/// A harness showing a single "Gain" knob over `0.0..=1.0`
+fn harness(value: f32, configure: fn(Knob<'_>) -> Knob<'_>) -> Harness<'static, f32> {
+    Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        move |ui, value| {
+            let knob = Knob::new(value, 0.0, 1.0, KnobStyle::Wiper)
+                .with_label("Gain", LabelPosition::Bottom);
+            ui.add(configure(knob));
+        },
+        value,
+    )
+}
+
+/// A harness showing a "Gain" knob over `range` next to some test state `T`
+///
+/// `configure` can hand the test state to the knob's hooks, and `record` reads
+/// the knob's response into it.
+fn recording_harness<T: 'static>(
+    range: RangeInclusive<f32>,
+    state: (f32, T),
+    configure: impl for<'a> Fn(Knob<'a>, &'a mut T) -> Knob<'a> + 'static,
+    record: impl Fn(&egui::Ui, &KnobResponse, &mut T) + 'static,
+) -> Harness<'static, (f32, T)> {
+    Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        move |ui, (value, test_state): &mut (f32, T)| {
+            let knob = Knob::new(value, *range.start(), *range.end(), KnobStyle::Wiper)
+                .with_label("Gain", LabelPosition::Bottom);
+            let response = configure(knob, test_state).show(ui);
+            record(ui, &response, test_state);
+        },
+        state,
+    )
+}
+
+/// Drags the knob with the primary button, moving the pointer by `offset` in steps
+fn drag<State>(harness: &mut Harness<'_, State>, offset: Vec2) {
+    let start = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(start);
+    harness.drag_at(start);
+    harness.step();
+    for i in 1..=4 {
+        harness.hover_at(start + offset * (i as f32 / 4.0));
+        harness.step();
+    }
+    harness.drop_at(start + offset);
+    harness.step();
+}
+
+#[test]
+fn reports_a_slider_with_its_value() {
+    let harness = harness(0.25, |knob| knob);
+    let node = harness.get_by_role_and_label(Role::Slider, "Gain");
+    assert_eq!(node.value().as_deref(), Some("0.25"));
+}
+
+#[test]
+fn dragging_up_increases_the_value() {
+    let mut harness = harness(0.5, |knob| knob);
+    drag(&mut harness, vec2(0.0, -40.0));
+    assert!(*harness.state() > 0.6, "value is {}", harness.state());
+}
+
+#[test]
+fn dragging_down_decreases_the_value() {
+    let mut harness = harness(0.5, |knob| knob);
+    drag(&mut harness, vec2(0.0, 40.0));
+    assert!(*harness.state() < 0.4, "value is {}", harness.state());
+}
+
+#[test]
+fn dragging_stays_in_range() {
+    let mut harness = harness(0.9, |knob| knob);
+    drag(&mut harness, vec2(0.0, -400.0));
+    assert_eq!(*harness.state(), 1.0);
+}
+
+#[test]
+#[allow(deprecated)]
+fn stepped_drag_lands_on_steps() {
+    let mut harness = harness(0.5, |knob| knob.with_step(Some(0.1)));
+    drag(&mut harness, vec2(0.0, -3.0));
+    let steps = *harness.state() * 10.0;
+    assert!(
+        (steps - steps.round()).abs() < 1e-4,
+        "value is {}",
+        harness.state()
+    );
+}
+
+#[test]
+fn scrolling_changes_the_value() {
+    let mut harness = harness(0.5, |knob| knob.with_middle_scroll());
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    harness.event(Event::MouseWheel {
+        unit: MouseWheelUnit::Point,
+        delta: vec2(0.0, 10.0),
+        phase: TouchPhase::Move,
+        modifiers: Modifiers::NONE,
+    });
+    harness.step();
+    assert!(*harness.state() > 0.5, "value is {}", harness.state());
+}
+
+#[test]
+fn scrolling_is_off_by_default() {
+    let mut harness = harness(0.5, |knob| knob);
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    harness.event(Event::MouseWheel {
+        unit: MouseWheelUnit::Point,
+        delta: vec2(0.0, 10.0),
+        phase: TouchPhase::Move,
+        modifiers: Modifiers::NONE,
+    });
+    harness.step();
+    assert_eq!(*harness.state(), 0.5);
+}
+
+#[test]
+fn double_click_resets_the_value() {
+    let mut harness = harness(0.8, |knob| knob.with_double_click_reset(0.25));
+    for _ in 0..2 {
+        harness.get_by_label("Gain").click();
+        harness.step();
+    }
+    assert_eq!(*harness.state(), 0.25);
+}
+
+#[test]
+fn idle_knob_leaves_the_value_untouched() {
+    let value = 0.123_456_79;
+    let mut harness = harness(value, |knob| knob.with_logarithmic_scaling());
+    harness.run_steps(3);
+    assert_eq!(harness.state().to_bits(), value.to_bits());
+}
+
+/// Records gestures as text, e.g. `"begin"`, `"set"`, `"end"`
+#[derive(Default)]
+struct Gestures(Vec<&'static str>);
+
+impl ParamGestureHandler for Gestures {
+    fn begin_edit(&mut self) {
+        self.0.push("begin");
+    }
+
+    fn perform_edit(&mut self, _value: f32, _normalized: f32) {
+        if self.0.last() != Some(&"set") {
+            self.0.push("set");
+        }
+    }
+
+    fn end_edit(&mut self) {
+        self.0.push("end");
+    }
+}
+
+#[test]
+fn drag_is_one_gesture() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, Gestures::default()),
+        |knob, gestures| knob.with_gesture_handler(gestures),
+        |_, _, _| {},
+    );
+    let start = harness.get_by_label("Gain").rect().center();
+    harness.drag_at(start);
+    harness.step();
+    for y in [10.0, 20.0, 30.0] {
+        harness.hover_at(start - vec2(0.0, y));
+        harness.step();
+    }
+    harness.drop_at(start - vec2(0.0, 30.0));
+    harness.step();
+    assert_eq!(harness.state().1.0, ["begin", "set", "end"]);
+}
+
+#[test]
+fn modifier_click_starts_learning() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, 0),
+        |knob, learned| {
+            knob.with_learn(
+                InvokeGesture::Click(Modifiers::CTRL | Modifiers::ALT),
+                || *learned += 1,
+            )
+        },
+        |_, _, _| {},
+    );
+    harness.get_by_label("Gain").click();
+    harness.step();
+    harness.step();
+    assert_eq!(harness.state().1, 0);
+
+    harness
+        .get_by_label("Gain")
+        .click_modifiers(Modifiers::CTRL | Modifiers::ALT);
+    harness.step();
+    harness.step();
+    assert_eq!(harness.state().1, 1);
+    assert_eq!(harness.state().0, 0.5);
+}
+
+#[test]
+fn drag_is_one_undo_entry() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, Vec::new()),
+        |knob, edits| knob.with_undo_hook(|edit| edits.push(edit)),
+        |_, _, _| {},
+    );
+    drag(&mut harness, vec2(0.0, -40.0));
+    let (value, edits) = harness.state();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].old_value, 0.5);
+    assert_eq!(edits[0].new_value, *value);
+}
+
+#[test]
+fn scroll_burst_is_one_undo_entry() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, Vec::new()),
+        |knob, edits| {
+            knob.with_undo_hook(|edit| edits.push(edit))
+                .with_middle_scroll()
+        },
+        |_, _, _| {},
+    );
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    for _ in 0..3 {
+        harness.event(Event::MouseWheel {
+            unit: MouseWheelUnit::Point,
+            delta: vec2(0.0, 10.0),
+            phase: TouchPhase::Move,
+            modifiers: Modifiers::NONE,
+        });
+        harness.step();
+    }
+    assert!(harness.state().1.is_empty());
+
+    for _ in 0..60 {
+        harness.step();
+    }
+    let (value, edits) = harness.state();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].old_value, 0.5);
+    assert_eq!(edits[0].new_value, *value);
+}
+
+#[test]
+fn reset_is_one_undo_entry() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.8, Vec::new()),
+        |knob, edits| {
+            knob.with_undo_hook(|edit| edits.push(edit))
+                .with_double_click_reset(0.25)
+        },
+        |_, _, _| {},
+    );
+    for _ in 0..2 {
+        harness.get_by_label("Gain").click();
+        harness.step();
+    }
+    let edits = &harness.state().1;
+    assert_eq!(edits.len(), 1);
+    assert_eq!((edits[0].old_value, edits[0].new_value), (0.8, 0.25));
+}
+
+#[test]
+fn paste_sets_the_hovered_knob() {
+    let mut harness = harness(0.5, |knob| knob.with_clipboard(true));
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    harness.event(Event::Paste("0.75".to_owned()));
+    harness.step();
+    assert_eq!(*harness.state(), 0.75);
+
+    harness.event(Event::Paste("12".to_owned()));
+    harness.step();
+    assert_eq!(*harness.state(), 1.0);
+}
+
+#[test]
+fn copy_puts_the_formatted_value_on_the_clipboard() {
+    let mut harness = harness(0.5, |knob| knob.with_clipboard(true).with_unit("dB"));
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    harness.event(Event::Copy);
+    harness.step();
+    let copied =
+        harness.output().platform_output.commands.iter().any(
+            |command| matches!(command, egui::OutputCommand::CopyText(text) if text == "0.50 dB"),
+        );
+    assert!(copied, "{:?}", harness.output().platform_output.commands);
+}
+
+#[test]
+fn clipboard_is_off_by_default() {
+    let mut harness = harness(0.5, |knob| knob);
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    harness.event(Event::Paste("0.75".to_owned()));
+    harness.step();
+    assert_eq!(*harness.state(), 0.5);
+}
+
+#[test]
+fn drag_reports_its_cause() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, None),
+        |knob, _| knob,
+        |_, response, cause| *cause = response.cause.or(*cause),
+    );
+    drag(&mut harness, vec2(0.0, -40.0));
+    assert_eq!(harness.state().1, Some(ChangeCause::Drag));
+}
+
+#[test]
+fn arrow_keys_step_the_focused_knob() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, None),
+        |knob, _| knob,
+        |_, response, cause| *cause = response.cause.or(*cause),
+    );
+    harness.get_by_label("Gain").focus();
+    harness.step();
+    harness.key_press(egui::Key::ArrowUp);
+    harness.step();
+    assert!(harness.state().0 > 0.5, "value is {}", harness.state().0);
+    assert_eq!(harness.state().1, Some(ChangeCause::Keyboard));
+
+    harness.key_press(egui::Key::ArrowDown);
+    harness.key_press(egui::Key::ArrowLeft);
+    harness.step();
+    assert!(harness.state().0 < 0.5, "value is {}", harness.state().0);
+}
+
+#[test]
+fn application_changes_are_external() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, None),
+        |knob, _| knob,
+        |_, response, cause| *cause = response.cause.or(*cause),
+    );
+    harness.step();
+    assert_eq!(harness.state().1, None);
+    harness.state_mut().0 = 0.25;
+    harness.step();
+    assert_eq!(harness.state().1, Some(ChangeCause::External));
+}
+
+#[test]
+fn animation_glides_to_the_target() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (value, recall): &mut (f32, bool)| {
+            let mut knob = Knob::new(value, 0.0, 1.0, KnobStyle::Wiper);
+            if std::mem::take(recall) {
+                knob = knob.animate_to(1.0, 0.5);
+            }
+            ui.add(knob);
+        },
+        (0.0, true),
+    );
+    for _ in 0..15 {
+        harness.step();
+    }
+    let halfway = harness.state().0;
+    assert!(halfway > 0.0 && halfway < 1.0, "value is {halfway}");
+
+    for _ in 0..30 {
+        harness.step();
+    }
+    assert_eq!(harness.state().0, 1.0);
+}
+
+/// What a knob gliding to `1.0` reported: whether it was marked changed with
+/// [`ChangeCause::Animation`], its gestures and its undo edits
+type AnimationReport = (bool, Gestures, Vec<ValueEdit>);
+
+#[test]
+fn animation_is_one_gesture_and_one_edit() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (value, recall, (animated, gestures, edits)): &mut (f32, bool, AnimationReport)| {
+            let mut knob = Knob::new(value, 0.0, 1.0, KnobStyle::Wiper)
+                .with_gesture_handler(&mut *gestures)
+                .with_undo_hook(|edit| edits.push(edit));
+            if std::mem::take(recall) {
+                knob = knob.animate_to(1.0, 0.25);
+            }
+            let response = knob.show(ui);
+            *animated |= response.changed() && response.cause == Some(ChangeCause::Animation);
+        },
+        (0.0, true, AnimationReport::default()),
+    );
+    harness.run_steps(30);
+    let (value, _, (animated, gestures, edits)) = harness.state();
+    assert_eq!(*value, 1.0);
+    assert!(animated);
+    assert_eq!(gestures.0, ["begin", "set", "end"]);
+    assert_eq!(edits.len(), 1);
+    assert_eq!((edits[0].old_value, edits[0].new_value), (0.0, 1.0));
+}
+
+#[test]
+fn automation_is_followed_until_touched() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (value, touches): &mut (f32, Vec<&'static str>)| {
+            let (mut on_touch, mut on_release) = (Vec::new(), Vec::new());
+            ui.add(
+                Knob::new(value, 0.0, 1.0, KnobStyle::Wiper)
+                    .with_label("Gain", LabelPosition::Bottom)
+                    .with_automation(Some(0.2))
+                    .with_touch(|| on_touch.push("touch"), || on_release.push("release")),
+            );
+            touches.extend(on_touch.into_iter().chain(on_release));
+        },
+        (0.5, Vec::new()),
+    );
+    harness.step();
+    assert_eq!(harness.state().0, 0.2);
+
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.drag_at(center);
+    harness.step();
+    for y in [10.0, 20.0, 30.0] {
+        harness.hover_at(center - vec2(0.0, y));
+        harness.step();
+    }
+    assert!(harness.state().0 > 0.2, "value is {}", harness.state().0);
+    assert_eq!(harness.state().1, ["touch"]);
+
+    harness.drop_at(center - vec2(0.0, 30.0));
+    harness.step();
+    harness.step();
+    assert_eq!(harness.state().0, 0.2);
+    assert_eq!(harness.state().1, ["touch", "release"]);
+}
+
+#[test]
+fn many_reports_the_changed_index() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (values, changed): &mut ([f32; 4], Vec<usize>)| {
+            let config = KnobConfig::new(KnobStyle::Dot).with_label("Step", LabelPosition::Bottom);
+            changed.extend(Knob::many(ui, values, 0.0..=1.0, &config).changed_indices());
+        },
+        ([0.5; 4], Vec::new()),
+    );
+    let start = harness
+        .get_all_by_label("Step")
+        .nth(2)
+        .unwrap()
+        .rect()
+        .center();
+    harness.drag_at(start);
+    harness.step();
+    harness.hover_at(start - vec2(0.0, 30.0));
+    harness.step();
+    harness.drop_at(start - vec2(0.0, 30.0));
+    harness.step();
+
+    let (values, changed) = harness.state();
+    assert_eq!(changed.first(), Some(&2));
+    assert!(changed.iter().all(|&index| index == 2));
+    assert_eq!([values[0], values[1], values[3]], [0.5; 3]);
+    assert!(values[2] > 0.5);
+}
+
+#[test]
+fn command_click_resets_to_the_default() {
+    let mut harness = harness(0.8, |knob| knob.with_default(0.25));
+    harness.get_by_label("Gain").click();
+    harness.step();
+    assert_eq!(*harness.state(), 0.8);
+
+    harness
+        .get_by_label("Gain")
+        .click_modifiers(Modifiers::COMMAND);
+    harness.step();
+    assert_eq!(*harness.state(), 0.25);
+}
+
+#[test]
+fn spring_return_is_part_of_the_drag_gesture() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, Gestures::default()),
+        |knob, gestures| {
+            knob.with_default(0.5)
+                .with_spring_return(true)
+                .with_gesture_handler(gestures)
+        },
+        |_, _, _| {},
+    );
+    drag(&mut harness, vec2(0.0, -40.0));
+    assert_eq!(harness.state().0, 0.5);
+    assert_eq!(harness.state().1.0, ["begin", "set", "end"]);
+}
+
+/// Opens the value editor with an Alt-click and replaces its text with `text`
+fn type_into_editor(harness: &mut Harness<'_, f32>, text: &str) {
+    harness.get_by_label("Gain").click_modifiers(Modifiers::ALT);
+    harness.step();
+    harness.step();
+    harness.event(Event::Text(text.to_owned()));
+    harness.step();
+}
+
+#[test]
+fn value_editor_commits_with_enter() {
+    let mut harness = harness(0.5, |knob| {
+        knob.with_value_editor(InvokeGesture::Click(Modifiers::ALT))
+    });
+    type_into_editor(&mut harness, "0.75");
+    assert_eq!(*harness.state(), 0.5);
+
+    harness.key_press(egui::Key::Enter);
+    harness.step();
+    assert_eq!(*harness.state(), 0.75);
+}
+
+#[test]
+fn value_editor_cancels_with_escape() {
+    let mut harness = harness(0.5, |knob| {
+        knob.with_value_editor(InvokeGesture::Click(Modifiers::ALT))
+    });
+    type_into_editor(&mut harness, "0.75");
+    harness.key_press(egui::Key::Escape);
+    harness.step();
+    harness.key_press(egui::Key::Enter);
+    harness.step();
+    assert_eq!(*harness.state(), 0.5);
+}
+
+#[test]
+fn value_editor_keeps_unparseable_text_open() {
+    let mut harness = harness(0.5, |knob| {
+        knob.with_value_editor(InvokeGesture::Click(Modifiers::ALT))
+    });
+    type_into_editor(&mut harness, "loud");
+    harness.key_press(egui::Key::Enter);
+    harness.step();
+    assert_eq!(*harness.state(), 0.5);
+
+    harness.key_press(egui::Key::Backspace);
+    harness.key_press(egui::Key::Backspace);
+    harness.key_press(egui::Key::Backspace);
+    harness.key_press(egui::Key::Backspace);
+    harness.event(Event::Text("0.25".to_owned()));
+    harness.key_press(egui::Key::Enter);
+    harness.step();
+    assert_eq!(*harness.state(), 0.25);
+}
+
+#[test]
+fn normalized_binding_maps_through_the_range() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (normalized, plain, external): &mut (f32, f32, bool)| {
+            let response = Knob::new(normalized, 20.0, 20_000.0, KnobStyle::Wiper)
+                .with_label("Gain", LabelPosition::Bottom)
+                .with_logarithmic_scaling()
+                .with_normalized_value(true)
+                .show(ui);
+            *plain = response.value;
+            *external |= response.cause == Some(ChangeCause::External);
+        },
+        (0.5, 0.0, false),
+    );
+    harness.step();
+    let centre = egui_knob::math::denormalize(0.5, 20.0, 20_000.0, true);
+    assert_eq!(harness.state().1, centre);
+
+    drag(&mut harness, vec2(0.0, -40.0));
+    harness.run_steps(3);
+    let (normalized, plain, external) = *harness.state();
+    assert!(
+        normalized > 0.6 && normalized <= 1.0,
+        "value is {normalized}"
+    );
+    assert!(plain > centre, "plain value is {plain}");
+    assert!(
+        !external,
+        "writing back the normalized value counted as external"
+    );
+}
+
+#[test]
+fn value_field_of_a_normalized_knob_shows_the_plain_value() {
+    let harness = Harness::builder().build_ui_state(
+        |ui, value: &mut f32| {
+            ui.add(
+                Knob::new(value, 0.0, 100.0, KnobStyle::Wiper)
+                    .with_label("Gain", LabelPosition::Bottom)
+                    .with_normalized_value(true)
+                    .with_value_field(),
+            );
+        },
+        0.5,
+    );
+    let field = harness.get_by_role(Role::SpinButton);
+    assert_eq!(field.value().as_deref(), Some("50"));
+}
+
+#[test]
+fn state_is_queryable_after_adding_the_knob() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, None),
+        |knob, _| knob,
+        |ui, response, state| *state = KnobState::load(ui.ctx(), response.id),
+    );
+    harness.step();
+    assert_eq!(harness.state().1, None);
+
+    let start = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(start);
+    harness.drag_at(start);
+    harness.step();
+    harness.hover_at(start + vec2(0.0, -20.0));
+    harness.step();
+    let state = harness.state().1.clone().expect("dragged knob has state");
+    assert!(state.is_dragged());
+    assert!(!state.is_editing());
+    assert_eq!(state.drag_start_value(), Some(0.5));
+
+    harness.drop_at(start + vec2(0.0, -20.0));
+    harness.run_steps(2);
+    assert_eq!(harness.state().1, None);
+}
+
+#[test]
+fn gauge_reports_its_displayed_value() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (value, displayed): &mut (f32, Option<f32>)| {
+            let response = Knob::new(value, 0.0, 10.0, KnobStyle::Wiper)
+                .with_gauge_mode(0.1)
+                .show(ui);
+            *displayed = KnobState::displayed_value(ui.ctx(), response.id);
+        },
+        (0.0, None),
+    );
+    harness.step();
+    assert_eq!(harness.state().1, Some(0.0));
+
+    harness.state_mut().0 = 10.0;
+    harness.step();
+    let displayed = harness.state().1.unwrap();
+    assert!(displayed > 0.0 && displayed < 10.0, "displayed {displayed}");
+
+    harness.run_steps(120);
+    assert_eq!(harness.state().1, Some(10.0));
+}
+
+#[test]
+fn indicator_follows_a_reset_in_the_same_frame() {
+    let mut harness = recording_harness(
+        20.0..=20_000.0,
+        (15_000.0, 0.0),
+        |knob, _| knob.with_logarithmic_scaling().with_default(1000.0),
+        |_, response, angle| *angle = KnobGeometry::from_response(response).unwrap().angle,
+    );
+    for _ in 0..2 {
+        harness.get_by_label("Gain").click();
+        harness.step();
+    }
+    let (value, reset_angle) = *harness.state();
+    assert_eq!(value, 1000.0);
+
+    harness.step();
+    assert_eq!(harness.state().1, reset_angle);
+}
+
+#[test]
+fn value_step_snaps_drags_to_whole_values() {
+    let mut harness = recording_harness(
+        0.0..=100.0,
+        (50.0, ()),
+        |knob, _| knob.with_step_by(1.0).with_middle_scroll(),
+        |_, _, _| {},
+    );
+    drag(&mut harness, vec2(0.0, -13.0));
+    let value = harness.state().0;
+    assert!(value > 50.0, "value is {value}");
+    assert_eq!(value, value.round());
+}
+
+#[test]
+fn value_step_moves_keys_and_wheel_by_one_step() {
+    let mut harness = recording_harness(
+        0.0..=100.0,
+        (50.0, ()),
+        |knob, _| knob.with_step_by(1.0).with_middle_scroll(),
+        |_, _, _| {},
+    );
+    harness.get_by_label("Gain").focus();
+    harness.step();
+    harness.key_press(egui::Key::ArrowUp);
+    harness.step();
+    assert_eq!(harness.state().0, 51.0);
+
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.event(Event::MouseWheel {
+        unit: MouseWheelUnit::Line,
+        delta: vec2(0.0, -3.0),
+        modifiers: Modifiers::NONE,
+        phase: TouchPhase::Move,
+    });
+    harness.step();
+    assert_eq!(harness.state().0, 50.0);
+}
+
+#[test]
+fn horizontal_scrolling_leaves_a_stepped_value() {
+    let mut harness = recording_harness(
+        0.0..=100.0,
+        (50.0, ()),
+        |knob, _| knob.with_step_by(1.0).with_middle_scroll(),
+        |_, _, _| {},
+    );
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.event(Event::MouseWheel {
+        unit: MouseWheelUnit::Line,
+        delta: vec2(-3.0, 0.0),
+        modifiers: Modifiers::NONE,
+        phase: TouchPhase::Move,
+    });
+    harness.step();
+    assert_eq!(harness.state().0, 50.0);
+}
+
+#[test]
+fn every_input_marks_the_response_changed() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, 0),
+        |knob, _| {
+            knob.with_default(0.25)
+                .with_middle_scroll()
+                .with_value_editor(InvokeGesture::Click(Modifiers::ALT))
+        },
+        |_, response, changes| *changes += usize::from(response.changed()),
+    );
+    let center = harness.get_by_label("Gain").rect().center();
+    harness.hover_at(center);
+    harness.step();
+    harness.event(Event::MouseWheel {
+        unit: MouseWheelUnit::Point,
+        delta: vec2(0.0, 10.0),
+        phase: TouchPhase::Move,
+        modifiers: Modifiers::NONE,
+    });
+    harness.step();
+    assert_eq!(harness.state().1, 1, "scroll");
+
+    for _ in 0..2 {
+        harness.get_by_label("Gain").click();
+        harness.step();
+    }
+    assert_eq!(harness.state().0, 0.25);
+    assert_eq!(harness.state().1, 2, "reset");
+
+    harness.get_by_label("Gain").focus();
+    harness.step();
+    harness.key_press(egui::Key::ArrowUp);
+    harness.step();
+    assert_eq!(harness.state().1, 3, "arrow key");
+
+    harness.get_by_label("Gain").click_modifiers(Modifiers::ALT);
+    harness.run_steps(2);
+    harness.event(Event::Text("0.75".to_owned()));
+    harness.key_press(egui::Key::Enter);
+    harness.step();
+    assert_eq!(harness.state().0, 0.75);
+    assert_eq!(harness.state().1, 4, "text entry");
+
+    drag(&mut harness, vec2(0.0, 40.0));
+    assert!(harness.state().1 > 4, "drag");
+}
+
+#[test]
+fn application_changes_do_not_mark_the_response_changed() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (0.5, 0),
+        |knob, _| {
+            knob.with_default(0.25)
+                .with_middle_scroll()
+                .with_value_editor(InvokeGesture::Click(Modifiers::ALT))
+        },
+        |_, response, changes| *changes += usize::from(response.changed()),
+    );
+    harness.step();
+    harness.state_mut().0 = 0.8;
+    harness.run_steps(2);
+    assert_eq!(harness.state().1, 0);
+}
+
+#[test]
+fn typing_in_the_value_field_is_one_stepped_gesture() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (value, gestures): &mut (f32, Gestures)| {
+            ui.add(
+                Knob::new(value, 0.0, 100.0, KnobStyle::Wiper)
+                    .with_label("Gain", LabelPosition::Bottom)
+                    .with_step_by(10.0)
+                    .with_gesture_handler(gestures)
+                    .with_value_field(),
+            );
+        },
+        (20.0, Gestures::default()),
+    );
+    harness.get_by_role(Role::SpinButton).click();
+    harness.run_steps(2);
+    harness.get_by_role(Role::SpinButton).type_text("47");
+    harness.step();
+    harness.key_press(egui::Key::Enter);
+    harness.run_steps(2);
+    assert_eq!(harness.state().0, 50.0);
+    assert_eq!(harness.state().1.0, ["begin", "set", "end"]);
+}
+
+#[test]
+fn out_of_range_value_is_drawn_at_the_end_and_kept() {
+    let mut at_max = recording_harness(
+        0.0..=1.0,
+        (1.0, (0.0, false)),
+        |knob, _| knob.with_out_of_range(OutOfRange::default()),
+        |_, response, (angle, clamped)| {
+            *angle = KnobGeometry::from_response(response).unwrap().angle;
+            *clamped |= response.clamped;
+        },
+    );
+    at_max.step();
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (1.5, (0.0, false)),
+        |knob, _| knob.with_out_of_range(OutOfRange::default()),
+        |_, response, (angle, clamped)| {
+            *angle = KnobGeometry::from_response(response).unwrap().angle;
+            *clamped |= response.clamped;
+        },
+    );
+    harness.run_steps(2);
+    assert_eq!(*harness.state(), (1.5, (at_max.state().1.0, false)));
+
+    harness.get_by_label("Gain").focus();
+    harness.step();
+    harness.key_press(egui::Key::ArrowDown);
+    harness.step();
+    let (value, (_, clamped)) = *harness.state();
+    assert!(value < 1.0, "value is {value}");
+    assert!(clamped);
+}
+
+#[test]
+fn clamp_policy_clamps_right_away() {
+    let mut harness = recording_harness(
+        0.0..=1.0,
+        (-2.0, (0.0, false)),
+        |knob, _| knob.with_out_of_range(OutOfRange::Clamp),
+        |_, response, (angle, clamped)| {
+            *angle = KnobGeometry::from_response(response).unwrap().angle;
+            *clamped |= response.clamped;
+        },
+    );
+    harness.step();
+    assert_eq!(harness.state().0, 0.0);
+    assert!(harness.state().1.1);
+}
+
+#[test]
+fn nan_value_takes_the_fallback() {
+    let mut knob = harness(f32::NAN, |knob| knob);
+    knob.step();
+    assert_eq!(*knob.state(), 0.0);
+
+    let mut knob = harness(f32::NAN, |knob| {
+        knob.with_default(0.5)
+            .with_nan_fallback(NanFallback::Default)
+    });
+    knob.step();
+    assert_eq!(*knob.state(), 0.5);
+
+    let mut knob = harness(f32::NAN, |knob| {
+        knob.with_nan_fallback(NanFallback::Value(2.0))
+    });
+    knob.step();
+    assert_eq!(*knob.state(), 1.0);
+}
+
+#[test]
+fn non_finite_ranges_ignore_input() {
+    for (min, max) in [
+        (f32::NAN, f32::NAN),
+        (0.0, f32::INFINITY),
+        (f32::NEG_INFINITY, f32::INFINITY),
+        (f32::NAN, 1.0),
+    ] {
+        for value in [0.5, f32::NAN] {
+            let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+                move |ui, value: &mut f32| {
+                    ui.add(
+                        Knob::new(value, min, max, KnobStyle::Wiper)
+                            .with_label("Gain", LabelPosition::Bottom)
+                            .with_default(0.25)
+                            .with_middle_scroll()
+                            .with_out_of_range(OutOfRange::Clamp),
+                    );
+                },
+                value,
+            );
+            harness.step();
+            let before = *harness.state();
+            drag(&mut harness, vec2(0.0, -40.0));
+            for _ in 0..2 {
+                harness.get_by_label("Gain").click();
+                harness.step();
+            }
+            assert_eq!(
+                harness.state().to_bits(),
+                before.to_bits(),
+                "{min}..={max} changed {before} to {}",
+                harness.state()
+            );
+        }
+    }
+}
+
+#[test]
+fn empty_range_parks_the_knob() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (value, inert): &mut (f32, bool)| {
+            let response = Knob::new(value, 2.0, 2.0, KnobStyle::Wiper)
+                .with_label("Gain", LabelPosition::Bottom)
+                .with_default(0.0)
+                .show(ui);
+            assert_eq!(response.normalized, 0.0);
+            *inert = response.inert;
+        },
+        (2.0, false),
+    );
+    harness.step();
+    assert!(harness.state().1);
+    let node = harness.get_by_label("Gain");
+    assert!(NodeT::accesskit_node(&node).is_disabled());
+
+    drag(&mut harness, vec2(0.0, -40.0));
+    for _ in 0..2 {
+        harness.get_by_label("Gain").click();
+        harness.step();
+    }
+    assert_eq!(harness.state().0, 2.0);
+}
+
+#[test]
+fn always_value_tooltip_shows_without_a_label() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui(|ui| {
+        let mut value = 0.3;
+        ui.add(
+            Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+                .with_tooltip(TooltipMode::AlwaysValue),
+        );
+    });
+    let center = harness.get_by_role(Role::Slider).rect().center();
+    harness.hover_at(center);
+    harness.run_steps(60);
+    harness.get_by_label("0.30");
+}
+
+/// Mirrors the "Units" example of the README
+#[test]
+fn unit_follows_the_value() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui(|ui| {
+        let mut value = 440.0;
+        ui.add(
+            Knob::new(&mut value, 20.0, 20000.0, KnobStyle::Wiper)
+                .with_unit("Hz")
+                .with_tooltip(TooltipMode::AlwaysValue),
+        );
+    });
+    let center = harness.get_by_role(Role::Slider).rect().center();
+    harness.hover_at(center);
+    harness.run_steps(60);
+    harness.get_by_label("440 Hz");
+}
+
+#[test]
+fn mirrored_sweep_turns_counter_clockwise() {
+    let mut harness = Harness::new_ui_state(
+        |ui, angles: &mut [f32; 2]| {
+            for (i, value) in [0.0, 1.0 / 3.0].into_iter().enumerate() {
+                let mut value = value;
+                let response = Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+                    .with_mirrored_sweep()
+                    .show(ui);
+                angles[i] = KnobGeometry::from_response(&response).unwrap().angle;
+            }
+        },
+        [0.0; 2],
+    );
+    harness.run();
+    let [start, third] = *harness.state();
+    assert!((Vec2::angled(start) - vec2(1.0, 0.0)).length() < 1e-4);
+    assert!((Vec2::angled(third) - vec2(0.0, -1.0)).length() < 1e-4);
+}
+
+/// A harness showing a "Gain" switch with three positions that advance on click
+fn switch_harness(selected: usize) -> Harness<'static, usize> {
+    Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, selected| {
+            ui.add(
+                RotarySwitch::new(selected, ["Off", "Low", "High"], KnobStyle::Skirted)
+                    .with_config(
+                        KnobConfig::new(KnobStyle::Skirted)
+                            .with_label("Gain", LabelPosition::Bottom),
+                    )
+                    .with_click_to_advance(true),
+            );
+        },
+        selected,
+    )
+}
+
+#[test]
+fn rotary_switch_shows_the_position_name() {
+    let harness = switch_harness(1);
+    let node = harness.get_by_role_and_label(Role::Slider, "Gain");
+    assert_eq!(node.value().as_deref(), Some("Low"));
+}
+
+#[test]
+fn rotary_switch_clicks_advance_and_wrap() {
+    let mut harness = switch_harness(1);
+    harness.get_by_label("Gain").click();
+    harness.step();
+    assert_eq!(*harness.state(), 2);
+    harness.get_by_label("Gain").click();
+    harness.step();
+    assert_eq!(*harness.state(), 0);
+
+    harness
+        .get_by_label("Gain")
+        .click_modifiers(Modifiers::SHIFT);
+    harness.step();
+    assert_eq!(*harness.state(), 2);
+}
+
+#[test]
+fn rotary_switch_drags_between_positions() {
+    let mut harness = switch_harness(0);
+    drag(&mut harness, vec2(0.0, -60.0));
+    assert!(*harness.state() > 0);
+    drag(&mut harness, vec2(0.0, 400.0));
+    assert_eq!(*harness.state(), 0);
+}
+
+/// A harness showing a jog wheel, keeping its position and the last response's speed
+/// and shuttle deflection
+fn jog_harness(configure: fn(JogWheel<'_>) -> JogWheel<'_>) -> Harness<'static, [f32; 3]> {
+    Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        move |ui, [position, velocity, shuttle]: &mut [f32; 3]| {
+            let jog = configure(
+                JogWheel::new(position, KnobStyle::Dot)
+                    .with_config(KnobConfig::new(KnobStyle::Dot).with_size(60.0)),
+            )
+            .show(ui);
+            (*velocity, *shuttle) = (jog.velocity, jog.shuttle);
+        },
+        [0.0; 3],
+    )
+}
+
+/// Drags clockwise along a quarter circle of `radius` around the wheel, from 3 to 6 o'clock
+fn turn_quarter<State>(harness: &mut Harness<'_, State>, radius: f32, release: bool) {
+    let center = harness.get_by_role(Role::Slider).rect().center();
+    let at = |t: f32| center + Vec2::angled(t * std::f32::consts::FRAC_PI_2) * radius;
+    harness.hover_at(at(0.0));
+    harness.drag_at(at(0.0));
+    harness.step();
+    for i in 1..=8 {
+        harness.hover_at(at(i as f32 / 8.0));
+        harness.step();
+    }
+    if release {
+        harness.drop_at(at(1.0));
+        harness.step();
+    }
+}
+
+#[test]
+fn jog_wheel_turns_with_the_pointer() {
+    let mut harness = jog_harness(|jog| jog);
+    turn_quarter(&mut harness, 20.0, true);
+    let [position, velocity, _] = *harness.state();
+    assert!(
+        position > 0.15 && position <= 0.25,
+        "position is {position}"
+    );
+    assert_eq!(velocity, 0.0);
+}
+
+#[test]
+fn jog_wheel_keeps_spinning_with_inertia() {
+    let mut harness = jog_harness(|jog| jog.with_inertia(0.5));
+    turn_quarter(&mut harness, 20.0, true);
+    let released_at = harness.state()[0];
+    harness.run_steps(10);
+    let [position, velocity, _] = *harness.state();
+    assert!(position > released_at, "position is {position}");
+    assert!(velocity > 0.0);
+}
+
+#[test]
+fn shuttle_ring_springs_back_on_release() {
+    let mut harness = jog_harness(|jog| jog.with_shuttle_ring(true));
+    turn_quarter(&mut harness, 33.0, false);
+    let [position, _, shuttle] = *harness.state();
+    assert_eq!(position, 0.0);
+    assert_eq!(shuttle, 1.0);
+
+    harness.drop_at(harness.get_by_role(Role::Slider).rect().center());
+    harness.step();
+    assert_eq!(harness.state()[2], 0.0);
+}
+
+#[test]
+fn slow_drags_move_a_stepped_range_end() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (low, high, rect): &mut (f32, f32, egui::Rect)| {
+            let response = ui.add(
+                Knob::new(high, 0.0, 100.0, KnobStyle::Wiper)
+                    .with_size(40.0)
+                    .with_step_by(1.0)
+                    .into_range(low),
+            );
+            *rect = response.rect;
+        },
+        (20.0, 80.0, egui::Rect::NOTHING),
+    );
+    harness.run();
+    let (min_angle, max_angle) = egui_knob::math::sweep_angles(0.25, 0.75);
+    // Just past the high end, so the drag grabs it even after the pointer moved a little
+    let high_angle = egui_knob::math::angle_at(0.8, min_angle, max_angle);
+    let start = harness.state().2.center() + Vec2::angled(high_angle + 0.4) * 15.0;
+
+    harness.hover_at(start);
+    harness.drag_at(start);
+    harness.step();
+    for i in 1..=40 {
+        harness.hover_at(start - vec2(0.0, 0.8 * i as f32));
+        harness.step();
+    }
+    harness.drop_at(start - vec2(0.0, 32.0));
+    harness.step();
+
+    let (low, high, _) = *harness.state();
+    assert_eq!(low, 20.0);
+    assert!(high > 85.0, "high is {high}");
+}
+
+#[test]
+fn link_toggle_is_reported_apart_from_the_knobs() {
+    let mut harness = Harness::builder().with_step_dt(1.0 / 60.0).build_ui_state(
+        |ui, (linked, changes): &mut (bool, [bool; 2])| {
+            let (mut left, mut right) = (0.2, 0.8);
+            let pair = StereoKnobs::new(&mut left, &mut right, linked, 0.0, 1.0, KnobStyle::Wiper)
+                .show(ui);
+            changes[0] |= pair.changed();
+            changes[1] |= pair.link.changed();
+        },
+        (false, [false; 2]),
+    );
+    harness.get_by_label("Link").click();
+    harness.run_steps(2);
+    assert_eq!(*harness.state(), (true, [false, true]));
+}
+
+#[test]
+fn range_knob_is_a_slider_with_both_ends() {
+    let harness = Harness::new_ui(|ui| {
+        let (mut low, mut high) = (20.0, 80.0);
+        ui.add(
+            Knob::new(&mut high, 0.0, 100.0, KnobStyle::Wiper)
+                .with_label("Band", LabelPosition::Bottom)
+                .into_range(&mut low),
+        );
+    });
+    let slider = harness.get_by_role(Role::Slider);
+    assert_eq!(slider.value().as_deref(), Some("20 – 80"));
+}
+
+#[test]
+fn single_position_switch_is_shown_enabled() {
+    let harness = Harness::new_ui(|ui| {
+        let mut selected = 0;
+        ui.add(RotarySwitch::new(
+            &mut selected,
+            ["Mono"],
+            KnobStyle::Skirted,
+        ));
+    });
+    let switch = harness.get_by_role(Role::Slider);
+    assert!(!switch.accesskit_node().is_disabled());
+    assert_eq!(switch.value().as_deref(), Some("Mono"));
+}
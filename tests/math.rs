@@ -0,0 +1,100 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use egui_knob::math::{
+    angle_at, angle_distance, clock_sweep_angles, denormalize, normalize, normalized_at_angle,
+    sweep_angles,
+};
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-5,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn sweep_starts_at_the_bottom() {
+    let (min, max) = sweep_angles(0.0, 0.5);
+    assert_close(min, FRAC_PI_2);
+    assert_close(max, FRAC_PI_2 + PI);
+}
+
+#[test]
+fn sweep_wraps_the_start_and_turns_negative_ranges_counter_clockwise() {
+    let (min, max) = sweep_angles(1.25, -0.5);
+    assert_close(min, FRAC_PI_2 + TAU * 0.25);
+    assert_close(max, min - PI);
+}
+
+#[test]
+fn clock_sweep_matches_the_normalized_sweep() {
+    let (min, max) = clock_sweep_angles((-135.0f32).to_radians(), 135.0f32.to_radians());
+    let (expected_min, expected_max) = sweep_angles(0.125, 0.75);
+    assert_close(min, expected_min);
+    assert_close(max, expected_max);
+}
+
+#[test]
+fn clock_sweep_reverses_with_its_ends() {
+    let (min, max) = clock_sweep_angles(PI, 0.0);
+    assert_close(min, FRAC_PI_2);
+    assert_close(max, min - PI);
+}
+
+#[test]
+fn angle_follows_the_normalized_position() {
+    let (min, max) = sweep_angles(0.125, 0.75);
+    assert_close(angle_at(0.0, min, max), min);
+    assert_close(angle_at(0.5, min, max), (min + max) / 2.0);
+    assert_close(angle_at(1.0, min, max), max);
+    assert_eq!(angle_at(f32::NAN, min, max), min);
+}
+
+#[test]
+fn angle_maps_back_to_the_normalized_position() {
+    for range in [0.75, -0.75] {
+        let (min, max) = sweep_angles(0.125, range);
+        for t in [0.0, 0.25, 0.6, 1.0] {
+            assert_close(normalized_at_angle(angle_at(t, min, max), min, max), t);
+        }
+    }
+}
+
+#[test]
+fn angle_distance_takes_the_short_way_round() {
+    assert_close(angle_distance(0.1, TAU - 0.1), 0.2);
+    assert_close(angle_distance(PI, 0.0), PI);
+    assert_close(angle_distance(3.0 * TAU + 1.0, 1.0), 0.0);
+}
+
+#[test]
+fn linear_mapping_round_trips() {
+    assert_close(normalize(0.0, -24.0, 24.0, false), 0.5);
+    assert_close(denormalize(0.25, -24.0, 24.0, false), -12.0);
+    for value in [20.0, 440.0, 20000.0] {
+        assert_close(
+            denormalize(normalize(value, 20.0, 20000.0, false), 20.0, 20000.0, false) / value,
+            1.0,
+        );
+    }
+}
+
+#[test]
+fn logarithmic_mapping_round_trips() {
+    assert_eq!(normalize(20.0, 20.0, 20000.0, true), 0.0);
+    assert_close(normalize(20000.0, 20.0, 20000.0, true), 1.0);
+    assert!(normalize(10010.0, 20.0, 20000.0, true) > 0.5);
+    for value in [20.0, 440.0, 20000.0] {
+        assert_close(
+            denormalize(normalize(value, 20.0, 20000.0, true), 20.0, 20000.0, true) / value,
+            1.0,
+        );
+    }
+}
+
+#[test]
+fn empty_range_maps_to_the_start() {
+    assert_eq!(normalize(3.0, 2.0, 2.0, false), 0.0);
+    assert_eq!(normalize(2.0, 2.0, 2.0, true), 0.0);
+    assert_eq!(denormalize(0.7, 2.0, 2.0, false), 2.0);
+}
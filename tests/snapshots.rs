@@ -0,0 +1,82 @@
+use egui_kittest::Harness;
+use egui_knob::{Knob, KnobStyle, LabelPosition, egui};
+
+/// Renders a labeled knob at 30% in each built-in style
+fn snapshot_style(name: &str, style: KnobStyle) {
+    let mut value = 0.3;
+    let mut harness = Harness::builder()
+        .with_size(egui::vec2(80.0, 80.0))
+        .build_ui(|ui| {
+            ui.add(
+                Knob::new(&mut value, 0.0, 1.0, style.clone())
+                    .with_size(48.0)
+                    .with_label("Gain", LabelPosition::Bottom),
+            );
+        });
+    harness.run();
+    harness.snapshot(name);
+}
+
+#[test]
+fn wiper() {
+    snapshot_style("wiper", KnobStyle::Wiper);
+}
+
+#[test]
+fn dot() {
+    snapshot_style("dot", KnobStyle::Dot);
+}
+
+#[test]
+fn skirted() {
+    snapshot_style("skirted", KnobStyle::Skirted);
+}
+
+#[test]
+fn multi_turn() {
+    let mut value = 0.8;
+    let mut harness = Harness::builder()
+        .with_size(egui::vec2(80.0, 80.0))
+        .build_ui(|ui| {
+            ui.add(
+                Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+                    .with_size(48.0)
+                    .with_sweep_range(0.0, 3.0),
+            );
+        });
+    harness.run();
+    harness.snapshot("multi_turn");
+}
+
+#[test]
+fn spread() {
+    let mut value = 0.5;
+    let mut harness = Harness::builder()
+        .with_size(egui::vec2(80.0, 80.0))
+        .build_ui(|ui| {
+            ui.add(
+                Knob::new(&mut value, 0.0, 1.0, KnobStyle::Spread)
+                    .with_size(48.0)
+                    .with_angle_range_degrees(-135.0, 135.0)
+                    .with_label("Width", LabelPosition::Bottom),
+            );
+        });
+    harness.run();
+    harness.snapshot("spread");
+}
+
+#[test]
+fn coarse_arcs() {
+    let mut value = 0.6;
+    let mut harness = Harness::builder()
+        .with_size(egui::vec2(80.0, 80.0))
+        .build_ui(|ui| {
+            ui.add(
+                Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+                    .with_size(48.0)
+                    .with_arc_segments(8),
+            );
+        });
+    harness.run();
+    harness.snapshot("coarse_arcs");
+}